@@ -0,0 +1,62 @@
+//! Config flags shared by every service binary.
+//!
+//! - `--print-config`: print the effective, redacted config and exit
+//! - `--print-config-docs`: print the Markdown env reference and exit (needs no valid config)
+
+use crate::{
+    ConfigError, ConfigLoader, LoadedConfig, ServiceConfig, ShipyardConfig, render_markdown,
+};
+
+pub const PRINT_CONFIG: &str = "--print-config";
+pub const PRINT_CONFIG_DOCS: &str = "--print-config-docs";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFlag {
+    PrintConfig,
+    PrintConfigDocs,
+}
+
+/// First config flag in `args` (program name excluded), if any.
+pub fn config_flag<I, A>(args: I) -> Option<ConfigFlag>
+where
+    I: IntoIterator<Item = A>,
+    A: AsRef<str>,
+{
+    args.into_iter().find_map(|arg| match arg.as_ref() {
+        PRINT_CONFIG => Some(ConfigFlag::PrintConfig),
+        PRINT_CONFIG_DOCS => Some(ConfigFlag::PrintConfigDocs),
+        _ => None,
+    })
+}
+
+/// Load `ShipyardConfig<S>` from the process environment, handling the config flags.
+///
+/// With a flag set this prints to stdout and exits the process with status 0;
+/// config errors are returned either way, so `--print-config` also fails fast.
+///
+/// ```ignore
+/// let loaded = shipyard_config::cli::load::<FulfilmentConfig>("FULFILMENT_", "fulfilment-api")?;
+/// ```
+pub fn load<S: ServiceConfig>(
+    prefix: &str,
+    service_name: &str,
+) -> Result<LoadedConfig<ShipyardConfig<S>>, ConfigError> {
+    let flag = config_flag(std::env::args().skip(1));
+
+    if flag == Some(ConfigFlag::PrintConfigDocs) {
+        let title = format!("Config reference: {service_name}");
+        print!(
+            "{}",
+            render_markdown(&title, &ShipyardConfig::<S>::schema(prefix))
+        );
+        std::process::exit(0);
+    }
+
+    let loaded = ConfigLoader::from_env().load_with::<S>(prefix)?;
+
+    if flag == Some(ConfigFlag::PrintConfig) {
+        print!("{}", loaded.render());
+        std::process::exit(0);
+    }
+    Ok(loaded)
+}
@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ConfigError, Secret, Validator};
+
+const DEFAULT_MAX_CONNECTIONS: u32 = 5;
+const DEFAULT_MIN_CONNECTIONS: u32 = 0;
+const DEFAULT_ACQUIRE_TIMEOUT_SECS: u64 = 5;
+const DEFAULT_IDLE_TIMEOUT_SECS: u64 = 600;
+const DEFAULT_MAX_LIFETIME_SECS: u64 = 1800;
+
+/// Postgres connection + pool settings (`DATABASE_URL`, `DB_*`).
+///
+/// Timeouts set to `0` are disabled (no idle reaping / no lifetime cap / no statement timeout).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DatabaseConfig {
+    /// Connection string (or `DATABASE_URL_FILE`); redacted in logs
+    #[serde(default, rename = "database_url")]
+    pub url: Option<Secret<String>>,
+
+    /// Upper bound on pooled connections
+    #[serde(default = "default_max_connections", rename = "db_max_connections")]
+    pub max_connections: u32,
+
+    /// Connections kept open even when idle
+    #[serde(default = "default_min_connections", rename = "db_min_connections")]
+    pub min_connections: u32,
+
+    /// Seconds to wait for a pooled connection (also bounds the initial connect)
+    #[serde(
+        default = "default_acquire_timeout_secs",
+        rename = "db_acquire_timeout_secs"
+    )]
+    pub acquire_timeout_secs: u64,
+
+    /// Seconds before an idle connection is closed
+    #[serde(default = "default_idle_timeout_secs", rename = "db_idle_timeout_secs")]
+    pub idle_timeout_secs: u64,
+
+    /// Seconds before a connection is recycled regardless of use
+    #[serde(default = "default_max_lifetime_secs", rename = "db_max_lifetime_secs")]
+    pub max_lifetime_secs: u64,
+
+    /// Server-side `statement_timeout` in milliseconds
+    #[serde(default, rename = "db_statement_timeout_ms")]
+    pub statement_timeout_ms: u64,
+
+    /// `application_name` reported to Postgres (shows up in `pg_stat_activity`)
+    #[serde(default, rename = "db_application_name")]
+    pub application_name: Option<String>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            min_connections: DEFAULT_MIN_CONNECTIONS,
+            acquire_timeout_secs: DEFAULT_ACQUIRE_TIMEOUT_SECS,
+            idle_timeout_secs: DEFAULT_IDLE_TIMEOUT_SECS,
+            max_lifetime_secs: DEFAULT_MAX_LIFETIME_SECS,
+            statement_timeout_ms: 0,
+            application_name: None,
+        }
+    }
+}
+
+fn default_max_connections() -> u32 {
+    DEFAULT_MAX_CONNECTIONS
+}
+
+fn default_min_connections() -> u32 {
+    DEFAULT_MIN_CONNECTIONS
+}
+
+fn default_acquire_timeout_secs() -> u64 {
+    DEFAULT_ACQUIRE_TIMEOUT_SECS
+}
+
+fn default_idle_timeout_secs() -> u64 {
+    DEFAULT_IDLE_TIMEOUT_SECS
+}
+
+fn default_max_lifetime_secs() -> u64 {
+    DEFAULT_MAX_LIFETIME_SECS
+}
+
+impl DatabaseConfig {
+    /// The URL, or a config error naming the env vars to set.
+    pub fn require_url(&self) -> Result<&Secret<String>, ConfigError> {
+        self.url.as_ref().ok_or_else(|| {
+            ConfigError::Validation(
+                "database_url is required (env: DATABASE_URL or DATABASE_URL_FILE)".to_string(),
+            )
+        })
+    }
+
+    pub(crate) fn validate(&self, v: &mut Validator<'_>) {
+        v.check(
+            "database_url",
+            self.url
+                .as_ref()
+                .is_none_or(|url| !url.expose().trim().is_empty()),
+            "non-empty Postgres URL when set (or DATABASE_URL_FILE)",
+        );
+
+        v.check(
+            "db_max_connections",
+            self.max_connections > 0,
+            "integer > 0",
+        );
+
+        v.check(
+            "db_min_connections",
+            self.min_connections <= self.max_connections,
+            format!("integer <= DB_MAX_CONNECTIONS ({})", self.max_connections),
+        );
+
+        v.check(
+            "db_acquire_timeout_secs",
+            self.acquire_timeout_secs > 0,
+            "seconds > 0",
+        );
+
+        v.check(
+            "db_application_name",
+            self.application_name
+                .as_ref()
+                .is_none_or(|name| !name.trim().is_empty()),
+            "non-empty string when set",
+        );
+    }
+
+    pub fn acquire_timeout(&self) -> Duration {
+        Duration::from_secs(self.acquire_timeout_secs)
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        (self.idle_timeout_secs > 0).then(|| Duration::from_secs(self.idle_timeout_secs))
+    }
+
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        (self.max_lifetime_secs > 0).then(|| Duration::from_secs(self.max_lifetime_secs))
+    }
+
+    pub fn statement_timeout(&self) -> Option<Duration> {
+        (self.statement_timeout_ms > 0).then(|| Duration::from_millis(self.statement_timeout_ms))
+    }
+}
+
+#[cfg(feature = "sqlx")]
+mod pg {
+    use std::str::FromStr;
+
+    use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+
+    use super::DatabaseConfig;
+    use crate::ConfigError;
+
+    impl DatabaseConfig {
+        /// Pool sizing + timeouts from this config.
+        ///
+        /// ```ignore
+        /// let db = cfg.database.pool_options()
+        ///     .connect_with(cfg.database.connect_options()?)
+        ///     .await?;
+        /// ```
+        pub fn pool_options(&self) -> PgPoolOptions {
+            PgPoolOptions::new()
+                .max_connections(self.max_connections)
+                .min_connections(self.min_connections)
+                .acquire_timeout(self.acquire_timeout())
+                .idle_timeout(self.idle_timeout())
+                .max_lifetime(self.max_lifetime())
+        }
+
+        /// Parsed URL plus `application_name` and `statement_timeout`.
+        pub fn connect_options(&self) -> Result<PgConnectOptions, ConfigError> {
+            // Never echo the URL: it usually carries the password.
+            let mut opts = PgConnectOptions::from_str(self.require_url()?.expose()).map_err(|_| {
+                ConfigError::Validation(
+                    "database_url is not a valid Postgres URL (env: DATABASE_URL or DATABASE_URL_FILE)"
+                        .to_string(),
+                )
+            })?;
+
+            if let Some(name) = &self.application_name {
+                opts = opts.application_name(name);
+            }
+            if let Some(timeout) = self.statement_timeout() {
+                opts = opts.options([("statement_timeout", timeout.as_millis().to_string())]);
+            }
+            Ok(opts)
+        }
+    }
+}
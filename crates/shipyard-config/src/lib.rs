@@ -2,17 +2,270 @@
 //!
 //! Why this crate exists:
 //! - Defines a stable runtime configuration contract for services.
-//! - Provides one golden-path loader (env → typed struct).
+//! - Provides one golden-path loader (defaults → config file → env → overrides → typed struct).
 //! - Fails fast at startup when config is invalid.
 //!
-//! NOTE: Add additional config fields only when concrete consumers require them.
+//! NOTE: Add additional config fields only when several services need them; service-only
+//! settings belong in a `ServiceConfig` section (`ShipyardConfig<S>`).
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub mod cli;
+mod database;
+mod fields;
+mod loader;
+mod policy;
+#[cfg(feature = "reload")]
+mod reload;
+mod report;
+mod schema;
+mod secret;
+mod units;
+pub use database::DatabaseConfig;
+pub use loader::{
+    CONFIG_FILE_ENV, ConfigLoader, LoadedConfig, ServiceConfig, ShipyardConfig, Source,
+};
+pub use policy::{PLATFORM_RULES, Rule};
+#[cfg(feature = "reload")]
+pub use reload::{DEFAULT_FILE_POLL_INTERVAL, ReloadOutcome, ReloadTriggers, Reloader};
+pub use report::{FieldError, Validator};
+pub use schema::{VarSchema, render_markdown};
+pub use secret::Secret;
+pub use units::{ByteSize, HumanDuration};
+
 const DEFAULT_SERVICE_PORT: u16 = 8080;
+const DEFAULT_SHUTDOWN_PRE_STOP_DELAY_SECS: u64 = 5;
+const DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS: u64 = 25;
+const DEFAULT_MAINTENANCE_RETRY_AFTER_SECS: u64 = 60;
+const DEFAULT_OTEL_METRIC_EXPORT_INTERVAL: HumanDuration = HumanDuration::from_secs(60);
+const DEFAULT_OTEL_EXPORTER_OTLP_TIMEOUT: HumanDuration = HumanDuration::from_secs(10);
+
+/// Documentation for one config key: platform keys in `KEYS`, service keys via `ServiceConfig::keys`.
+///
+/// Feeds error reports, the schema and the generated env reference.
+#[derive(Debug, Clone, Copy)]
+pub struct KeySpec {
+    /// Field name (lowercase env var, without the service prefix)
+    pub name: &'static str,
+    /// Expected format, e.g. "TCP port in 1..=65535"
+    pub expected: &'static str,
+    /// One line for the env reference
+    pub description: &'static str,
+    /// Redact the value in error reports and config dumps
+    pub secret: bool,
+}
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq, Default)]
+impl KeySpec {
+    pub const fn new(
+        name: &'static str,
+        expected: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            expected,
+            description,
+            secret: false,
+        }
+    }
+
+    pub const fn secret(self) -> Self {
+        Self {
+            secret: true,
+            ..self
+        }
+    }
+}
+
+/// Every platform key; file and override keys must be one of these (or a service key).
+pub(crate) const KEYS: &[KeySpec] = &[
+    KeySpec::new(
+        "env",
+        "one of dev, test, prod",
+        "Runtime environment; selects the policy rules",
+    ),
+    KeySpec::new(
+        "service_port",
+        "TCP port in 1..=65535",
+        "HTTP port the service listens on",
+    ),
+    KeySpec::new(
+        "otel_service_name",
+        "non-empty string",
+        "`service.name` on traces, metrics and logs (unset: the binary's own name)",
+    ),
+    KeySpec::new(
+        "otel_exporter_otlp_endpoint",
+        "non-empty URL",
+        "OTLP endpoint for traces/metrics export; required in prod",
+    ),
+    KeySpec::new(
+        "otel_exporter_otlp_protocol",
+        "one of grpc, http/protobuf",
+        "OTLP transport; with `http/protobuf` the endpoint is the collector's HTTP port (4318)",
+    ),
+    KeySpec::new(
+        "otel_exporter_otlp_headers",
+        "comma-separated key=value pairs (values percent-encoded)",
+        "Headers on every OTLP export request, e.g. collector auth; prefer `OTEL_EXPORTER_OTLP_HEADERS_FILE`",
+    )
+    .secret(),
+    KeySpec::new(
+        "otel_exporter_otlp_compression",
+        "one of none, gzip (gzip needs grpc)",
+        "Compression of OTLP export requests",
+    ),
+    KeySpec::new(
+        "otel_exporter_otlp_timeout",
+        "duration between 1s and 5m",
+        "Timeout of each OTLP export request",
+    ),
+    KeySpec::new(
+        "otel_exporter_otlp_certificate",
+        "non-empty file path",
+        "PEM CA certificate trusted for `https` OTLP endpoints (on top of the system roots)",
+    ),
+    KeySpec::new(
+        "otel_resource_attributes",
+        "comma-separated key=value pairs (values percent-encoded)",
+        "Extra resource attributes on traces, metrics and logs, e.g. `team=fulfilment,region=eu-west-1`",
+    ),
+    KeySpec::new(
+        "otel_metrics_exporter",
+        "one of none, otlp",
+        "`otlp` also pushes metrics to the OTLP endpoint (`/metrics` scraping stays on)",
+    ),
+    KeySpec::new(
+        "otel_metric_export_interval",
+        "duration between 1s and 1h",
+        "How often metrics are pushed when `OTEL_METRICS_EXPORTER=otlp`",
+    ),
+    KeySpec::new(
+        "otel_traces_sampler",
+        "one of always_on, always_off, traceidratio, parentbased_always_on, parentbased_always_off, parentbased_traceidratio",
+        "Head sampling for traces",
+    ),
+    KeySpec::new(
+        "otel_traces_sampler_arg",
+        "ratio between 0 and 1",
+        "Sampled share for the `*traceidratio` samplers (unset: 1.0)",
+    ),
+    KeySpec::new(
+        "traces_keep_errors",
+        "true or false",
+        "Export spans that end with an error even when sampling dropped them",
+    ),
+    KeySpec::new(
+        "traces_keep_routes",
+        "comma-separated path prefixes",
+        "Always sample requests under these paths, e.g. `/api/v1/orders`",
+    ),
+    KeySpec::new(
+        "otel_logs_exporter",
+        "one of none, otlp",
+        "`otlp` also ships logs to the OTLP endpoint, correlated with traces (stdout stays on)",
+    ),
+    KeySpec::new(
+        "log_filter",
+        "tracing filter directives",
+        "Log filter (`RUST_LOG` syntax, e.g. `info,fulfilment_api=debug`); reloadable",
+    ),
+    KeySpec::new(
+        "log_format",
+        "one of json, pretty, compact, logfmt",
+        "Stdout log format; unset: `pretty` in dev, `json` otherwise",
+    ),
+    KeySpec::new(
+        "log_span_list",
+        "true or false",
+        "Include every span in scope, not only the current one (json, logfmt)",
+    ),
+    KeySpec::new(
+        "log_target",
+        "true or false",
+        "Include the event target (module path)",
+    ),
+    KeySpec::new(
+        "log_file_line",
+        "true or false",
+        "Include the source file and line of each event",
+    ),
+    KeySpec::new(
+        "log_flatten_span_fields",
+        "true or false",
+        "Put span fields (`request_id`, `path`, ...) at the top level of each event (json, logfmt)",
+    ),
+    KeySpec::new(
+        "database_url",
+        "non-empty Postgres URL",
+        "Postgres connection string; prefer `DATABASE_URL_FILE` outside local dev",
+    )
+    .secret(),
+    KeySpec::new(
+        "db_max_connections",
+        "integer > 0",
+        "Upper bound on pooled connections",
+    ),
+    KeySpec::new(
+        "db_min_connections",
+        "integer <= DB_MAX_CONNECTIONS",
+        "Connections kept open even when idle",
+    ),
+    KeySpec::new(
+        "db_acquire_timeout_secs",
+        "seconds > 0",
+        "Wait for a pooled connection (also bounds the initial connect)",
+    ),
+    KeySpec::new(
+        "db_idle_timeout_secs",
+        "seconds (0 disables)",
+        "Close connections idle for this long",
+    ),
+    KeySpec::new(
+        "db_max_lifetime_secs",
+        "seconds (0 disables)",
+        "Recycle connections after this long",
+    ),
+    KeySpec::new(
+        "db_statement_timeout_ms",
+        "milliseconds (0 disables)",
+        "Sent as the Postgres `statement_timeout`",
+    ),
+    KeySpec::new(
+        "db_application_name",
+        "non-empty string",
+        "`application_name` shown in `pg_stat_activity`",
+    ),
+    KeySpec::new(
+        "shutdown_pre_stop_delay_secs",
+        "seconds",
+        "Keep serving (with `/readyz` returning 503) after SIGTERM",
+    ),
+    KeySpec::new(
+        "shutdown_drain_timeout_secs",
+        "seconds > 0",
+        "Hard deadline for draining in-flight requests on shutdown",
+    ),
+    KeySpec::new(
+        "maintenance_file",
+        "non-empty file path",
+        "File polled every 5s for the maintenance mode (`off` | `read_only` | `full`)",
+    ),
+    KeySpec::new(
+        "maintenance_retry_after_secs",
+        "seconds",
+        "`Retry-After` on `503 MAINTENANCE` responses",
+    ),
+    KeySpec::new(
+        "admin_token",
+        "at least 16 characters",
+        "Bearer token for `/admin/*` endpoints; unset disables them. Prefer `ADMIN_TOKEN_FILE`",
+    )
+    .secret(),
+];
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Environment {
     #[default]
@@ -21,7 +274,96 @@ pub enum Environment {
     Prod,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+impl Environment {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Environment::Dev => "dev",
+            Environment::Test => "test",
+            Environment::Prod => "prod",
+        }
+    }
+}
+
+/// Where metrics go besides the `/metrics` scrape endpoint.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MetricsExporter {
+    /// Scrape only
+    #[default]
+    None,
+    /// Also push to `OTEL_EXPORTER_OTLP_ENDPOINT`
+    Otlp,
+}
+
+/// OTLP transport (`OTEL_EXPORTER_OTLP_PROTOCOL` values).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+pub enum OtlpProtocol {
+    #[default]
+    #[serde(rename = "grpc")]
+    Grpc,
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+}
+
+/// OTLP request compression (`OTEL_EXPORTER_OTLP_COMPRESSION` values).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum OtlpCompression {
+    #[default]
+    None,
+    Gzip,
+}
+
+/// Head sampler for traces (`OTEL_TRACES_SAMPLER` values).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TracesSampler {
+    AlwaysOn,
+    AlwaysOff,
+    #[serde(rename = "traceidratio")]
+    TraceIdRatio,
+    #[default]
+    ParentbasedAlwaysOn,
+    ParentbasedAlwaysOff,
+    #[serde(rename = "parentbased_traceidratio")]
+    ParentbasedTraceIdRatio,
+}
+
+impl TracesSampler {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TracesSampler::AlwaysOn => "always_on",
+            TracesSampler::AlwaysOff => "always_off",
+            TracesSampler::TraceIdRatio => "traceidratio",
+            TracesSampler::ParentbasedAlwaysOn => "parentbased_always_on",
+            TracesSampler::ParentbasedAlwaysOff => "parentbased_always_off",
+            TracesSampler::ParentbasedTraceIdRatio => "parentbased_traceidratio",
+        }
+    }
+}
+
+/// Stdout log format (`LOG_FORMAT` values).
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    Json,
+    Pretty,
+    Compact,
+    Logfmt,
+}
+
+/// Where logs go besides stdout.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogsExporter {
+    /// Stdout only
+    #[default]
+    None,
+    /// Also ship to `OTEL_EXPORTER_OTLP_ENDPOINT`
+    Otlp,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AppConfig {
     /// Runtime environment (dev/test/prod)
     #[serde(default)]
@@ -31,21 +373,151 @@ pub struct AppConfig {
     #[serde(default = "default_service_port")]
     pub service_port: u16,
 
-    /// OTLP endpoint for traces/metrics export (wired later by observability work)
+    /// `service.name` resource attribute (`OTEL_SERVICE_NAME`); unset uses the binary's name
+    #[serde(default)]
+    pub otel_service_name: Option<String>,
+
+    /// OTLP endpoint for traces/metrics export
     #[serde(default)]
     pub otel_exporter_otlp_endpoint: Option<String>,
+
+    /// OTLP transport (`OTEL_EXPORTER_OTLP_PROTOCOL`)
+    #[serde(default)]
+    pub otel_exporter_otlp_protocol: OtlpProtocol,
+
+    /// OTLP request headers, `key=value,...` (`OTEL_EXPORTER_OTLP_HEADERS` or `*_FILE`)
+    #[serde(default)]
+    pub otel_exporter_otlp_headers: Option<Secret<String>>,
+
+    /// OTLP request compression (`OTEL_EXPORTER_OTLP_COMPRESSION`)
+    #[serde(default)]
+    pub otel_exporter_otlp_compression: OtlpCompression,
+
+    /// Timeout per OTLP export request (`OTEL_EXPORTER_OTLP_TIMEOUT`, e.g. `10s`)
+    #[serde(default = "default_otel_exporter_otlp_timeout")]
+    pub otel_exporter_otlp_timeout: HumanDuration,
+
+    /// CA file for `https` OTLP endpoints (`OTEL_EXPORTER_OTLP_CERTIFICATE`)
+    #[serde(default)]
+    pub otel_exporter_otlp_certificate: Option<String>,
+
+    /// Extra resource attributes, `key=value,...` (`OTEL_RESOURCE_ATTRIBUTES`)
+    #[serde(default)]
+    pub otel_resource_attributes: Option<String>,
+
+    /// Push metrics over OTLP too (`OTEL_METRICS_EXPORTER`)
+    #[serde(default)]
+    pub otel_metrics_exporter: MetricsExporter,
+
+    /// Push interval for OTLP metrics (`OTEL_METRIC_EXPORT_INTERVAL`, e.g. `60s`)
+    #[serde(default = "default_otel_metric_export_interval")]
+    pub otel_metric_export_interval: HumanDuration,
+
+    /// Head sampler for traces (`OTEL_TRACES_SAMPLER`)
+    #[serde(default)]
+    pub otel_traces_sampler: TracesSampler,
+
+    /// Ratio for the `*traceidratio` samplers (`OTEL_TRACES_SAMPLER_ARG`); unset means 1.0
+    #[serde(default)]
+    pub otel_traces_sampler_arg: Option<f64>,
+
+    /// Export error spans even when the head sampler dropped them
+    #[serde(default)]
+    pub traces_keep_errors: bool,
+
+    /// Comma-separated path prefixes whose requests are always sampled
+    #[serde(default)]
+    pub traces_keep_routes: Option<String>,
+
+    /// Ship logs over OTLP too (`OTEL_LOGS_EXPORTER`)
+    #[serde(default)]
+    pub otel_logs_exporter: LogsExporter,
+
+    /// Log filter directives (`RUST_LOG` syntax); unset falls back to `RUST_LOG`, then `info`
+    #[serde(default)]
+    pub log_filter: Option<String>,
+
+    /// Stdout log format (`LOG_FORMAT`); unset picks one by `env`, see `AppConfig::log_format`
+    #[serde(default)]
+    pub log_format: Option<LogFormat>,
+
+    /// Include every span in scope in each log line
+    #[serde(default)]
+    pub log_span_list: bool,
+
+    /// Include the event target in each log line
+    #[serde(default = "default_log_target")]
+    pub log_target: bool,
+
+    /// Include source file and line in each log line
+    #[serde(default)]
+    pub log_file_line: bool,
+
+    /// Put span fields at the top level of each log line
+    #[serde(default)]
+    pub log_flatten_span_fields: bool,
+
+    /// Postgres settings (`DATABASE_URL`, `DB_*`); parsed from the same keys by the loader
+    #[serde(skip)]
+    pub database: DatabaseConfig,
+
+    /// Seconds to keep serving (with `/readyz` returning 503) after SIGTERM
+    #[serde(default = "default_shutdown_pre_stop_delay_secs")]
+    pub shutdown_pre_stop_delay_secs: u64,
+
+    /// Hard deadline in seconds for draining in-flight requests on shutdown
+    #[serde(default = "default_shutdown_drain_timeout_secs")]
+    pub shutdown_drain_timeout_secs: u64,
+
+    /// File polled for the maintenance mode (`off` | `read_only` | `full`); unset disables the watch
+    #[serde(default)]
+    pub maintenance_file: Option<String>,
+
+    /// `Retry-After` seconds returned while in maintenance mode
+    #[serde(default = "default_maintenance_retry_after_secs")]
+    pub maintenance_retry_after_secs: u64,
+
+    /// Bearer token required by `/admin/*` endpoints; unset disables them
+    #[serde(default)]
+    pub admin_token: Option<Secret<String>>,
 }
 
 fn default_service_port() -> u16 {
     DEFAULT_SERVICE_PORT
 }
 
+fn default_otel_exporter_otlp_timeout() -> HumanDuration {
+    DEFAULT_OTEL_EXPORTER_OTLP_TIMEOUT
+}
+
+fn default_otel_metric_export_interval() -> HumanDuration {
+    DEFAULT_OTEL_METRIC_EXPORT_INTERVAL
+}
+
+fn default_log_target() -> bool {
+    true
+}
+
+fn default_shutdown_pre_stop_delay_secs() -> u64 {
+    DEFAULT_SHUTDOWN_PRE_STOP_DELAY_SECS
+}
+
+fn default_shutdown_drain_timeout_secs() -> u64 {
+    DEFAULT_SHUTDOWN_DRAIN_TIMEOUT_SECS
+}
+
+fn default_maintenance_retry_after_secs() -> u64 {
+    DEFAULT_MAINTENANCE_RETRY_AFTER_SECS
+}
+
 impl AppConfig {
-    /// Load config from process environment variables (fail fast)
+    /// Load config from the process environment, layered over `SHIPYARD_CONFIG_FILE` (fail fast)
+    ///
+    /// Use `ConfigLoader` directly for overrides or to see where values came from.
     pub fn from_env() -> Result<Self, ConfigError> {
-        let cfg: AppConfig = envy::from_env().map_err(ConfigError::Parse)?;
-        cfg.validate()?;
-        Ok(cfg)
+        ConfigLoader::from_env()
+            .load()
+            .map(LoadedConfig::into_inner)
     }
 
     /// Load config from an iterator of key/value pairs (useful for tests)
@@ -55,14 +527,50 @@ impl AppConfig {
         K: AsRef<str>,
         V: AsRef<str>,
     {
-        let owned: Vec<(String, String)> = iter
-            .into_iter()
-            .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
-            .collect();
-
-        let cfg: AppConfig = envy::from_iter(owned).map_err(ConfigError::Parse)?;
-        cfg.validate()?;
-        Ok(cfg)
+        ConfigLoader::from_kv(iter)
+            .load()
+            .map(LoadedConfig::into_inner)
+    }
+
+    /// `TRACES_KEEP_ROUTES` split into prefixes (empty entries dropped).
+    pub fn traces_keep_routes(&self) -> Vec<String> {
+        self.traces_keep_routes
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|route| !route.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// `OTEL_SERVICE_NAME`, else `default` (the binary's stable name).
+    pub fn service_name<'a>(&'a self, default: &'a str) -> &'a str {
+        self.otel_service_name.as_deref().unwrap_or(default)
+    }
+
+    /// `LOG_FORMAT`, else `pretty` in dev (readable in a terminal) and `json` elsewhere (shipped).
+    pub fn log_format(&self) -> LogFormat {
+        self.log_format.unwrap_or(match self.env {
+            Environment::Dev => LogFormat::Pretty,
+            Environment::Test | Environment::Prod => LogFormat::Json,
+        })
+    }
+
+    /// `OTEL_EXPORTER_OTLP_HEADERS` as `(name, value)` pairs, values percent-decoded. Do not log.
+    pub fn otlp_headers(&self) -> Vec<(String, String)> {
+        self.otel_exporter_otlp_headers
+            .as_ref()
+            .and_then(|raw| parse_key_values(raw.expose()))
+            .unwrap_or_default()
+    }
+
+    /// `OTEL_RESOURCE_ATTRIBUTES` as `(key, value)` pairs, values percent-decoded.
+    pub fn resource_attributes(&self) -> Vec<(String, String)> {
+        self.otel_resource_attributes
+            .as_deref()
+            .and_then(parse_key_values)
+            .unwrap_or_default()
     }
 
     /// Dev-like config that cannot drift from defaults + validation.
@@ -71,22 +579,152 @@ impl AppConfig {
             .expect("default config should always be valid")
     }
 
-    fn validate(&self) -> Result<(), ConfigError> {
-        if self.service_port == 0 {
-            return Err(ConfigError::Validation(
-                "service_port must be in 1..=65535 (env: SERVICE_PORT)".to_string(),
-            ));
+    pub(crate) fn validate(&self, v: &mut Validator<'_>) {
+        v.check(
+            "service_port",
+            self.service_port != 0,
+            "TCP port in 1..=65535",
+        );
+
+        v.check(
+            "otel_service_name",
+            self.otel_service_name
+                .as_ref()
+                .is_none_or(|name| !name.trim().is_empty()),
+            "non-empty string when set",
+        );
+
+        v.check(
+            "otel_exporter_otlp_endpoint",
+            self.otel_exporter_otlp_endpoint
+                .as_ref()
+                .is_none_or(|ep| !ep.trim().is_empty()),
+            "non-empty URL when set",
+        );
+
+        v.check(
+            "otel_exporter_otlp_headers",
+            self.otel_exporter_otlp_headers
+                .as_ref()
+                .is_none_or(|raw| parse_key_values(raw.expose()).is_some()),
+            "comma-separated key=value pairs with non-empty keys",
+        );
+
+        v.check(
+            "otel_exporter_otlp_compression",
+            self.otel_exporter_otlp_compression == OtlpCompression::None
+                || self.otel_exporter_otlp_protocol == OtlpProtocol::Grpc,
+            "`none` unless OTEL_EXPORTER_OTLP_PROTOCOL=grpc",
+        );
+
+        v.check_range(
+            "otel_exporter_otlp_timeout",
+            &self.otel_exporter_otlp_timeout,
+            HumanDuration::from_secs(1)..=HumanDuration::from_secs(300),
+        );
+
+        v.check(
+            "otel_exporter_otlp_certificate",
+            self.otel_exporter_otlp_certificate
+                .as_ref()
+                .is_none_or(|path| !path.trim().is_empty()),
+            "non-empty file path when set",
+        );
+
+        v.check(
+            "otel_resource_attributes",
+            self.otel_resource_attributes
+                .as_deref()
+                .is_none_or(|raw| parse_key_values(raw).is_some()),
+            "comma-separated key=value pairs with non-empty keys",
+        );
+
+        v.check(
+            "otel_metrics_exporter",
+            self.otel_metrics_exporter == MetricsExporter::None
+                || self.otel_exporter_otlp_endpoint.is_some(),
+            "`none` unless OTEL_EXPORTER_OTLP_ENDPOINT is set",
+        );
+
+        v.check_range(
+            "otel_metric_export_interval",
+            &self.otel_metric_export_interval,
+            HumanDuration::from_secs(1)..=HumanDuration::from_secs(3600),
+        );
+
+        if let Some(ratio) = self.otel_traces_sampler_arg {
+            v.check_range("otel_traces_sampler_arg", &ratio, 0.0..=1.0);
         }
 
-        if let Some(ep) = &self.otel_exporter_otlp_endpoint
-            && ep.trim().is_empty()
-        {
-            return Err(ConfigError::Validation(
-                "otel_exporter_otlp_endpoint must not be empty when set (env: OTEL_EXPORTER_OTLP_ENDPOINT)".to_string(),
-            ));
+        v.check(
+            "otel_logs_exporter",
+            self.otel_logs_exporter == LogsExporter::None
+                || self.otel_exporter_otlp_endpoint.is_some(),
+            "`none` unless OTEL_EXPORTER_OTLP_ENDPOINT is set",
+        );
+
+        v.check(
+            "log_filter",
+            self.log_filter
+                .as_ref()
+                .is_none_or(|filter| !filter.trim().is_empty()),
+            "non-empty filter when set",
+        );
+
+        self.database.validate(v);
+
+        v.check(
+            "shutdown_drain_timeout_secs",
+            self.shutdown_drain_timeout_secs > 0,
+            "seconds > 0",
+        );
+
+        v.check(
+            "maintenance_file",
+            self.maintenance_file
+                .as_ref()
+                .is_none_or(|path| !path.trim().is_empty()),
+            "non-empty file path when set",
+        );
+
+        v.check(
+            "admin_token",
+            self.admin_token
+                .as_ref()
+                .is_none_or(|token| token.expose().trim().len() >= 16),
+            "at least 16 characters when set",
+        );
+    }
+}
+
+/// `k=v,k2=v2` (W3C baggage-like); `None` when an entry is malformed.
+fn parse_key_values(raw: &str) -> Option<Vec<(String, String)>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            Some((key.to_string(), percent_decode(value.trim())?))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut out = Vec::with_capacity(s.len());
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        if b == b'%' {
+            let hex = [bytes.next()?, bytes.next()?];
+            out.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            out.push(b);
         }
-        Ok(())
     }
+    String::from_utf8(out).ok()
 }
 
 #[derive(Debug, Error)]
@@ -94,6 +732,13 @@ pub enum ConfigError {
     #[error("failed to parse configuration from environment: {0}")]
     Parse(envy::Error),
 
+    #[error("failed to load config file {path}: {message}")]
+    File { path: String, message: String },
+
     #[error("invalid configuration: {0}")]
     Validation(String),
+
+    /// Every invalid, missing or unknown value found in one load.
+    #[error("invalid configuration: {}", report::format_errors(.0))]
+    Invalid(Vec<FieldError>),
 }
@@ -0,0 +1,583 @@
+//! Layered configuration loading.
+//!
+//! Precedence (lowest → highest):
+//! 1. struct defaults (`#[serde(default)]`)
+//! 2. config file (`SHIPYARD_CONFIG_FILE`, TOML or YAML, flat keys)
+//! 3. environment variables (`FOO_FILE` reads `FOO` from a mounted file)
+//! 4. explicit overrides (`ConfigLoader::set`)
+//!
+//! Services add their own section under a prefix with `ConfigLoader::load_with`.
+//!
+//! Every layer is reduced to `key → string` and parsed once by envy, so a value
+//! means the same thing whichever layer it comes from.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+use serde::{
+    Deserialize, Serialize,
+    de::{DeserializeOwned, IgnoredAny},
+};
+
+use crate::{
+    AppConfig, ConfigError, DatabaseConfig, Environment, FieldError, KEYS, KeySpec, PLATFORM_RULES,
+    Rule, Validator,
+    fields::struct_fields,
+    policy::violations,
+    report::{field_error, suggest},
+    schema::{app_values, effective, flatten},
+};
+
+/// Env var naming the config file layer.
+pub const CONFIG_FILE_ENV: &str = "SHIPYARD_CONFIG_FILE";
+
+/// Layer that supplied an effective config value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env,
+    /// `FOO_FILE` env var pointing at a mounted secret file.
+    EnvFile(PathBuf),
+    Override,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => f.write_str("default"),
+            Source::File(path) => write!(f, "file:{}", path.display()),
+            Source::Env => f.write_str("env"),
+            Source::EnvFile(path) => write!(f, "env_file:{}", path.display()),
+            Source::Override => f.write_str("override"),
+        }
+    }
+}
+
+/// Builder for layered config loading.
+///
+/// ```ignore
+/// let loaded = ConfigLoader::from_env().set("service_port", "9090").load()?;
+/// tracing::info!(config = ?loaded, "config.loaded");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    file: Option<PathBuf>,
+    env: Vec<(String, String)>,
+    overrides: Vec<(String, String)>,
+}
+
+impl ConfigLoader {
+    /// Loader over the process environment (file taken from `SHIPYARD_CONFIG_FILE`).
+    pub fn from_env() -> Self {
+        Self::from_kv(std::env::vars())
+    }
+
+    /// Loader over explicit env-style pairs (useful for tests).
+    pub fn from_kv<I, K, V>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let env: Vec<(String, String)> = iter
+            .into_iter()
+            .map(|(k, v)| (k.as_ref().to_string(), v.as_ref().to_string()))
+            .collect();
+
+        let file = env
+            .iter()
+            .find(|(k, _)| k == CONFIG_FILE_ENV)
+            .map(|(_, v)| v.trim())
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from);
+
+        Self {
+            file,
+            env,
+            overrides: Vec::new(),
+        }
+    }
+
+    /// Use this config file instead of `SHIPYARD_CONFIG_FILE`.
+    pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.file = Some(path.into());
+        self
+    }
+
+    /// Highest-precedence value for `key` (field name, e.g. `service_port`).
+    pub fn set(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.overrides.push((key.into(), value.into()));
+        self
+    }
+
+    /// Merge all layers, parse and validate (fail fast).
+    ///
+    /// Every problem found is reported at once in `ConfigError::Invalid`.
+    pub fn load(self) -> Result<LoadedConfig, ConfigError> {
+        let keys = platform_keys();
+        let mut errors = Vec::new();
+
+        let values = self.merge(&keys, &mut errors)?;
+        let config = parse_app(&keys, &values, &mut errors);
+
+        let config = match config {
+            Some(config) if errors.is_empty() => config,
+            _ => return Err(ConfigError::Invalid(errors)),
+        };
+
+        enforce_policy(config.env, violations(config.env, &config, PLATFORM_RULES))?;
+
+        let sources = collect_sources(&keys, values);
+        Ok(LoadedConfig {
+            effective: effective(&AppConfig::schema(), &app_values(&config), &sources),
+            config,
+            sources,
+        })
+    }
+
+    /// Like `load`, plus a service-defined section read from `<PREFIX>*` keys.
+    ///
+    /// ```ignore
+    /// #[derive(Debug, Deserialize)]
+    /// struct Fulfilment { #[serde(default)] outbox_batch_size: i64 }
+    /// impl ServiceConfig for Fulfilment {}
+    ///
+    /// // FULFILMENT_OUTBOX_BATCH_SIZE=100 (or `fulfilment_outbox_batch_size = 100` in the file)
+    /// let cfg = ConfigLoader::from_env().load_with::<Fulfilment>("FULFILMENT_")?;
+    /// ```
+    pub fn load_with<S: ServiceConfig>(
+        self,
+        prefix: &str,
+    ) -> Result<LoadedConfig<ShipyardConfig<S>>, ConfigError> {
+        let prefix = normalize_prefix(prefix);
+        let lower = prefix.to_ascii_lowercase();
+
+        let service_keys: Vec<String> = struct_fields::<S>()
+            .iter()
+            .map(|field| format!("{lower}{field}"))
+            .collect();
+        let mut keys = platform_keys();
+        keys.extend(service_keys.iter().cloned());
+
+        let mut errors = Vec::new();
+        let values = self.merge(&keys, &mut errors)?;
+
+        let app = parse_app(&platform_keys(), &values, &mut errors);
+        let service = parse_section::<S>(&prefix, &service_keys, &values, &mut errors);
+        if let Some(service) = &service {
+            service.validate(&mut Validator::new(&prefix, &values, &mut errors));
+        }
+
+        let (app, service) = match (app, service) {
+            (Some(app), Some(service)) if errors.is_empty() => (app, service),
+            _ => return Err(ConfigError::Invalid(errors)),
+        };
+
+        let mut broken = violations(app.env, &app, PLATFORM_RULES);
+        broken.extend(violations(app.env, &service, S::rules()));
+        enforce_policy(app.env, broken)?;
+
+        let sources = collect_sources(&keys, values);
+        let mut dumped = app_values(&app);
+        dumped.extend(flatten(&lower, &service));
+        Ok(LoadedConfig {
+            effective: effective(&ShipyardConfig::<S>::schema(&prefix), &dumped, &sources),
+            config: ShipyardConfig { app, service },
+            sources,
+        })
+    }
+
+    /// Apply file → env → overrides for `keys` (lowercase), recording each value's layer.
+    ///
+    /// Only an unreadable or unparseable config file aborts; everything else is collected.
+    fn merge(
+        self,
+        keys: &[String],
+        errors: &mut Vec<FieldError>,
+    ) -> Result<BTreeMap<String, (String, Source)>, ConfigError> {
+        let mut values: BTreeMap<String, (String, Source)> = BTreeMap::new();
+
+        if let Some(path) = &self.file {
+            for (key, value) in read_file(path)? {
+                match known_key(keys, &key) {
+                    Some(known) => {
+                        values.insert(known, (value, Source::File(path.clone())));
+                    }
+                    None => errors.push(unknown_key(key, keys, Source::File(path.clone()))),
+                }
+            }
+        }
+
+        let mut from_env: BTreeMap<String, (String, Source)> = BTreeMap::new();
+        for (key, value) in self.env {
+            let (known, value, source) = if let Some(known) = known_key(keys, &key) {
+                (known, value, Source::Env)
+            } else if let Some(known) = key
+                .strip_suffix("_FILE")
+                .and_then(|stem| known_key(keys, stem))
+            {
+                let path = PathBuf::from(value);
+                match read_secret_file(&path) {
+                    Ok(secret) => (known, secret, Source::EnvFile(path)),
+                    Err(e) => {
+                        errors.push(FieldError {
+                            key,
+                            value: Some(path.display().to_string()),
+                            source: Some(Source::Env),
+                            expected: "path to a readable file".to_string(),
+                            hint: Some(e.to_string()),
+                        });
+                        continue;
+                    }
+                }
+            } else {
+                // Unknown env vars are expected (PATH, HOME, ...); only near-misses of
+                // our own keys sharing their first segment (SERVICE_POTR) are reported.
+                if let Some(k) = suggest(&key, keys).filter(|k| same_first_segment(&key, k)) {
+                    errors.push(FieldError {
+                        key,
+                        value: None,
+                        source: Some(Source::Env),
+                        expected: "a known config variable".to_string(),
+                        hint: Some(format!("did you mean {}?", k.to_ascii_uppercase())),
+                    });
+                }
+                continue;
+            };
+
+            if let Some(previous) = from_env.insert(known.clone(), (value, source)) {
+                let var = known.to_ascii_uppercase();
+                from_env.insert(known, previous);
+                errors.push(FieldError {
+                    key: format!("{var}_FILE"),
+                    value: None,
+                    source: Some(Source::Env),
+                    expected: format!("either {var} or {var}_FILE, not both"),
+                    hint: None,
+                });
+            }
+        }
+        values.extend(from_env);
+
+        for (key, value) in self.overrides {
+            match known_key(keys, &key) {
+                Some(known) => {
+                    values.insert(known, (value, Source::Override));
+                }
+                None => errors.push(unknown_key(key, keys, Source::Override)),
+            }
+        }
+
+        Ok(values)
+    }
+}
+
+/// Platform config plus a service-defined section (see `ConfigLoader::load_with`).
+#[derive(Debug, Clone)]
+pub struct ShipyardConfig<S> {
+    pub app: AppConfig,
+    pub service: S,
+}
+
+impl<S: ServiceConfig> ShipyardConfig<S> {
+    /// Load from the process environment, layered over `SHIPYARD_CONFIG_FILE` (fail fast)
+    pub fn from_env(prefix: &str) -> Result<Self, ConfigError> {
+        ConfigLoader::from_env()
+            .load_with(prefix)
+            .map(LoadedConfig::into_inner)
+    }
+
+    /// Load from an iterator of key/value pairs (useful for tests)
+    pub fn from_kv<I, K, V>(prefix: &str, iter: I) -> Result<Self, ConfigError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        ConfigLoader::from_kv(iter)
+            .load_with(prefix)
+            .map(LoadedConfig::into_inner)
+    }
+}
+
+/// A service-defined config section.
+///
+/// Fields map to `<PREFIX><FIELD>` env vars; use `#[serde(default = ...)]` like `AppConfig`.
+/// `validate` runs after parsing and its failures are reported together with the platform's.
+/// `Serialize` feeds the schema defaults and `--print-config`.
+pub trait ServiceConfig: DeserializeOwned + Serialize + 'static {
+    /// Record rule violations with `Validator::check` (field names without the prefix).
+    fn validate(&self, _v: &mut Validator<'_>) {}
+
+    /// Environment policy for this section, checked after `PLATFORM_RULES`.
+    fn rules() -> &'static [Rule<Self>] {
+        &[]
+    }
+
+    /// Docs for the section's fields (names without the prefix), used by the schema.
+    fn keys() -> &'static [KeySpec] {
+        &[]
+    }
+}
+
+/// Effective config plus the layer that set each value.
+///
+/// `Debug` prints the config followed by a `key → source` map covering every field.
+#[derive(Clone)]
+pub struct LoadedConfig<C = AppConfig> {
+    pub config: C,
+    sources: BTreeMap<String, Source>,
+    /// Schema-ordered `key → value` with secrets redacted (`None`: unset)
+    effective: Vec<(String, Option<String>)>,
+}
+
+impl<C> LoadedConfig<C> {
+    /// Layer that set `key` (`Source::Default` when no layer did).
+    pub fn source_of(&self, key: &str) -> Option<&Source> {
+        self.sources.get(key)
+    }
+
+    pub fn into_inner(self) -> C {
+        self.config
+    }
+
+    /// Effective config as `KEY=value  # source` lines, secrets redacted (`--print-config`).
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for (key, value) in &self.effective {
+            let source = self.sources.get(key).unwrap_or(&Source::Default);
+            let value = value.as_deref().unwrap_or("");
+            let unset = if value.is_empty() { ", unset" } else { "" };
+            out.push_str(&format!(
+                "{}={value}  # {source}{unset}\n",
+                key.to_ascii_uppercase()
+            ));
+        }
+        out
+    }
+}
+
+impl<C: fmt::Debug> fmt::Debug for LoadedConfig<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        struct Sources<'a>(&'a BTreeMap<String, Source>);
+
+        impl fmt::Debug for Sources<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_map()
+                    .entries(self.0.iter().map(|(k, s)| (k, s.to_string())))
+                    .finish()
+            }
+        }
+
+        f.debug_struct("LoadedConfig")
+            .field("config", &self.config)
+            .field("sources", &Sources(&self.sources))
+            .finish()
+    }
+}
+
+/// Policy runs on an otherwise valid config; all violated rules are reported together.
+fn enforce_policy(env: Environment, violations: Vec<String>) -> Result<(), ConfigError> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+    Err(ConfigError::Validation(format!(
+        "policy violations for env {}: {}",
+        env.as_str(),
+        violations.join("; ")
+    )))
+}
+
+fn platform_keys() -> Vec<String> {
+    KEYS.iter().map(|k| k.name.to_string()).collect()
+}
+
+/// `FULFILMENT`, `fulfilment_` → `FULFILMENT_`
+pub(crate) fn normalize_prefix(prefix: &str) -> String {
+    let mut prefix = prefix.trim().to_ascii_uppercase();
+    if !prefix.ends_with('_') {
+        prefix.push('_');
+    }
+    prefix
+}
+
+fn parse_app(
+    keys: &[String],
+    values: &BTreeMap<String, (String, Source)>,
+    errors: &mut Vec<FieldError>,
+) -> Option<AppConfig> {
+    // envy can't parse `#[serde(flatten)]` sections, so each section is its own pass.
+    let config = parse_section::<AppConfig>("", keys, values, errors);
+    let database = parse_section::<DatabaseConfig>("", keys, values, errors);
+
+    let mut config = config?;
+    config.database = database?;
+    config.validate(&mut Validator::new("", values, errors));
+    Some(config)
+}
+
+/// Parse one section, recording every bad or missing value instead of stopping at the first.
+///
+/// Each value is first parsed on its own so a failure is pinned to its key; the
+/// good values are then parsed together to find missing required fields.
+fn parse_section<T: DeserializeOwned>(
+    prefix: &str,
+    keys: &[String],
+    values: &BTreeMap<String, (String, Source)>,
+    errors: &mut Vec<FieldError>,
+) -> Option<T> {
+    let parse = |pairs: Vec<(String, String)>| envy::prefixed(prefix).from_iter::<_, T>(pairs);
+
+    let mut good = Vec::new();
+    for key in keys {
+        let Some((value, _)) = values.get(key) else {
+            continue;
+        };
+        let pair = (key.to_ascii_uppercase(), value.clone());
+        match parse(vec![pair.clone()]) {
+            Err(envy::Error::Custom(msg)) => {
+                errors.push(field_error(key, values, expected_format(key, &msg), None));
+            }
+            _ => good.push(pair),
+        }
+    }
+
+    let mut missing = false;
+    // Bounded: each round either succeeds or fills in one more missing field.
+    for _ in 0..=keys.len() {
+        match parse(good.clone()) {
+            Ok(section) => return (!missing).then_some(section),
+            Err(envy::Error::MissingValue(field)) => {
+                let key = format!("{}{field}", prefix.to_ascii_lowercase());
+                // A set-but-invalid value was already reported above.
+                if !values.contains_key(&key) {
+                    errors.push(field_error(
+                        &key,
+                        values,
+                        "a value".to_string(),
+                        Some("required, no default".to_string()),
+                    ));
+                }
+                missing = true;
+                // Placeholder so the next round surfaces the next missing field.
+                good.push((key.to_ascii_uppercase(), "0".to_string()));
+            }
+            Err(envy::Error::Custom(_)) => return None,
+        }
+    }
+    None
+}
+
+/// Expected format for `key`: the documented one for platform keys, serde's otherwise.
+fn expected_format(key: &str, msg: &str) -> String {
+    KEYS.iter()
+        .find(|k| k.name == key)
+        .map(|k| k.expected.to_string())
+        .unwrap_or_else(|| {
+            // envy appends "while parsing value '<raw>' provided by <VAR>"; the raw
+            // value may be secret and is reported (redacted) separately.
+            msg.split(" while parsing value")
+                .next()
+                .unwrap_or(msg)
+                .to_string()
+        })
+}
+
+fn unknown_key(key: String, keys: &[String], source: Source) -> FieldError {
+    FieldError {
+        hint: suggest(&key, keys).map(|k| format!("did you mean `{k}`?")),
+        key,
+        value: None,
+        source: Some(source),
+        expected: "a known config key".to_string(),
+    }
+}
+
+fn same_first_segment(var: &str, key: &str) -> bool {
+    let first = |s: &str| s.split('_').next().unwrap_or("").to_ascii_lowercase();
+    first(var) == first(key)
+}
+
+fn collect_sources(
+    keys: &[String],
+    mut values: BTreeMap<String, (String, Source)>,
+) -> BTreeMap<String, Source> {
+    keys.iter()
+        .map(|k| {
+            let source = values
+                .remove(k)
+                .map_or(Source::Default, |(_, source)| source);
+            (k.clone(), source)
+        })
+        .collect()
+}
+
+fn known_key(keys: &[String], key: &str) -> Option<String> {
+    keys.iter().find(|k| k.eq_ignore_ascii_case(key)).cloned()
+}
+
+/// Read a `*_FILE` secret; a single trailing newline (as written by most tools) is dropped.
+fn read_secret_file(path: &Path) -> Result<String, ConfigError> {
+    let mut value = std::fs::read_to_string(path).map_err(|e| ConfigError::File {
+        path: path.display().to_string(),
+        message: e.to_string(),
+    })?;
+    if value.ends_with('\n') {
+        value.pop();
+        if value.ends_with('\r') {
+            value.pop();
+        }
+    }
+    Ok(value)
+}
+
+/// Flat scalar values only; nested tables are rejected with a clear error.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum FileValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Other(IgnoredAny),
+}
+
+fn read_file(path: &Path) -> Result<Vec<(String, String)>, ConfigError> {
+    let file_err = |message: String| ConfigError::File {
+        path: path.display().to_string(),
+        message,
+    };
+
+    let raw = std::fs::read_to_string(path).map_err(|e| file_err(e.to_string()))?;
+
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let table: BTreeMap<String, FileValue> = match ext {
+        "toml" => toml::from_str(&raw).map_err(|e| file_err(e.to_string()))?,
+        "yaml" | "yml" => serde_yaml::from_str(&raw).map_err(|e| file_err(e.to_string()))?,
+        _ => {
+            return Err(file_err(
+                "unsupported extension (expected .toml, .yaml or .yml)".to_string(),
+            ));
+        }
+    };
+
+    table
+        .into_iter()
+        .map(|(key, value)| {
+            let value = match value {
+                FileValue::Bool(b) => b.to_string(),
+                FileValue::Int(i) => i.to_string(),
+                FileValue::Float(f) => f.to_string(),
+                FileValue::Str(s) => s,
+                FileValue::Other(_) => {
+                    return Err(file_err(format!("`{key}` must be a scalar value")));
+                }
+            };
+            Ok((key, value))
+        })
+        .collect()
+}
@@ -0,0 +1,242 @@
+//! Hot-reloadable settings (feature `reload`).
+//!
+//! A service picks the subset of its config that is safe to change at runtime
+//! (log filter, poll intervals, limits) and publishes it through a
+//! `tokio::sync::watch` channel. Reloads re-run the full loader, so a reloaded
+//! value is validated exactly like one read at startup; a failed reload keeps
+//! the current value.
+//!
+//! ```ignore
+//! let reloader = Reloader::new(initial, || {
+//!     ConfigLoader::from_env().load_with::<Svc>("SVC_").map(|l| Settings::from(&l.config))
+//! });
+//! let mut rx = reloader.subscribe();
+//! reloader.spawn(ReloadTriggers::from_env());
+//! ```
+
+use std::{
+    fmt,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, SystemTime},
+};
+
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::{CONFIG_FILE_ENV, ConfigError};
+
+/// How often the config file's modification time is checked.
+pub const DEFAULT_FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+type LoadFn<R> = dyn Fn() -> Result<R, ConfigError> + Send + Sync;
+type ObserveFn = dyn Fn(ReloadOutcome) + Send + Sync;
+
+/// Result of one reload attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReloadOutcome {
+    /// New settings were validated and published
+    Applied,
+    /// Config loaded fine but the reloadable settings did not change
+    Unchanged,
+    /// Config was invalid; the current settings stay in place
+    Failed,
+}
+
+impl ReloadOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReloadOutcome::Applied => "applied",
+            ReloadOutcome::Unchanged => "unchanged",
+            ReloadOutcome::Failed => "failed",
+        }
+    }
+}
+
+/// What triggers a reload.
+#[derive(Debug, Clone)]
+pub struct ReloadTriggers {
+    /// Reload on SIGHUP (unix only)
+    pub sighup: bool,
+    /// Reload when this file's modification time changes
+    pub file: Option<PathBuf>,
+    pub file_poll_interval: Duration,
+}
+
+impl ReloadTriggers {
+    /// SIGHUP plus the file named by `SHIPYARD_CONFIG_FILE`, if set.
+    pub fn from_env() -> Self {
+        Self {
+            sighup: true,
+            file: std::env::var_os(CONFIG_FILE_ENV)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+            file_poll_interval: DEFAULT_FILE_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Publishes a reloadable settings subset `R`; cheap to clone.
+#[derive(Clone)]
+pub struct Reloader<R> {
+    tx: Arc<watch::Sender<R>>,
+    load: Arc<LoadFn<R>>,
+    observe: Option<Arc<ObserveFn>>,
+    applied: Arc<AtomicU64>,
+    failed: Arc<AtomicU64>,
+}
+
+impl<R> fmt::Debug for Reloader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reloader")
+            .field("applied", &self.applied.load(Ordering::Relaxed))
+            .field("failed", &self.failed.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+impl<R> Reloader<R>
+where
+    R: Clone + PartialEq + fmt::Debug + Send + Sync + 'static,
+{
+    /// `initial` is what startup loaded; `load` re-reads and validates config.
+    pub fn new(
+        initial: R,
+        load: impl Fn() -> Result<R, ConfigError> + Send + Sync + 'static,
+    ) -> Self {
+        let (tx, _) = watch::channel(initial);
+        Self {
+            tx: Arc::new(tx),
+            load: Arc::new(load),
+            observe: None,
+            applied: Arc::new(AtomicU64::new(0)),
+            failed: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Called after every reload attempt, e.g. to count outcomes in metrics.
+    pub fn observe(mut self, f: impl Fn(ReloadOutcome) + Send + Sync + 'static) -> Self {
+        self.observe = Some(Arc::new(f));
+        self
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<R> {
+        self.tx.subscribe()
+    }
+
+    pub fn current(&self) -> R {
+        self.tx.borrow().clone()
+    }
+
+    /// Reloads applied / failed since startup.
+    pub fn counts(&self) -> (u64, u64) {
+        (
+            self.applied.load(Ordering::Relaxed),
+            self.failed.load(Ordering::Relaxed),
+        )
+    }
+
+    /// Re-run the loader and publish the result if it changed; logs the outcome.
+    pub fn reload(&self) -> ReloadOutcome {
+        let outcome = match (self.load)() {
+            Ok(next) => {
+                let changed = self.tx.send_if_modified(|current| {
+                    if *current == next {
+                        return false;
+                    }
+                    tracing::info!(from = ?current, to = ?next, "config.reload_applied");
+                    *current = next;
+                    true
+                });
+                if changed {
+                    self.applied.fetch_add(1, Ordering::Relaxed);
+                    ReloadOutcome::Applied
+                } else {
+                    tracing::info!("config.reload_unchanged");
+                    ReloadOutcome::Unchanged
+                }
+            }
+            Err(e) => {
+                self.failed.fetch_add(1, Ordering::Relaxed);
+                tracing::warn!(error = %e, "config.reload_failed");
+                ReloadOutcome::Failed
+            }
+        };
+
+        if let Some(observe) = &self.observe {
+            observe(outcome);
+        }
+        outcome
+    }
+
+    /// Reload whenever one of `triggers` fires.
+    pub fn spawn(&self, triggers: ReloadTriggers) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut hup = Hangup::new(triggers.sighup);
+            let mut seen = modified(triggers.file.as_ref());
+            let mut poll = tokio::time::interval(triggers.file_poll_interval);
+            poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+            loop {
+                tokio::select! {
+                    () = hup.recv() => {
+                        tracing::info!(trigger = "sighup", "config.reload_requested");
+                    }
+                    _ = poll.tick(), if triggers.file.is_some() => {
+                        let now = modified(triggers.file.as_ref());
+                        if now == seen {
+                            continue;
+                        }
+                        seen = now;
+                        tracing::info!(trigger = "file", "config.reload_requested");
+                    }
+                }
+                this.reload();
+            }
+        })
+    }
+}
+
+fn modified(path: Option<&PathBuf>) -> Option<SystemTime> {
+    std::fs::metadata(path?).ok()?.modified().ok()
+}
+
+/// SIGHUP stream; never fires when disabled or unsupported.
+struct Hangup {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl Hangup {
+    fn new(enabled: bool) -> Self {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            let signal = enabled.then(|| signal(SignalKind::hangup())).and_then(|s| {
+                s.inspect_err(|e| tracing::warn!(error = %e, "config.sighup_unavailable"))
+                    .ok()
+            });
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = enabled;
+            Self {}
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = &mut self.signal {
+            if signal.recv().await.is_some() {
+                return;
+            }
+            self.signal = None;
+        }
+        std::future::pending::<()>().await
+    }
+}
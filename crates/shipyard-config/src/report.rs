@@ -0,0 +1,180 @@
+//! Accumulated config errors: every problem in one report, not one per deploy.
+
+use std::{collections::BTreeMap, fmt, ops::RangeInclusive};
+
+use crate::{KEYS, Source};
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Name fragments treated as secret in service sections (platform keys are flagged in `KEYS`).
+const SECRET_HINTS: &[&str] = &["password", "secret", "token", "credential", "private_key"];
+
+/// One invalid, missing or unknown config value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldError {
+    /// Env var name (file keys are reported as written in the file)
+    pub key: String,
+    /// Offending value (`[REDACTED]` for secrets); `None` when unset
+    pub value: Option<String>,
+    /// Where the value came from; `None` when unset
+    pub source: Option<Source>,
+    /// Expected format, e.g. "TCP port in 1..=65535"
+    pub expected: String,
+    /// Suggested fix, e.g. "did you mean SERVICE_PORT?"
+    pub hint: Option<String>,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.key)?;
+        if let Some(value) = &self.value {
+            write!(f, "={value:?}")?;
+        }
+        if let Some(source) = &self.source {
+            write!(f, " ({source})")?;
+        }
+        write!(f, ": expected {}", self.expected)?;
+        if let Some(hint) = &self.hint {
+            write!(f, " ({hint})")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn format_errors(errors: &[FieldError]) -> String {
+    let mut out = format!("{} problem(s)", errors.len());
+    for err in errors {
+        out.push_str("\n  - ");
+        out.push_str(&err.to_string());
+    }
+    out
+}
+
+/// Collects validation failures for a config section.
+///
+/// `check` looks up the raw value and its source itself, so call sites only
+/// state the rule:
+///
+/// ```ignore
+/// v.check("outbox_batch_size", self.outbox_batch_size > 0, "integer > 0");
+/// ```
+pub struct Validator<'a> {
+    prefix: String,
+    values: &'a BTreeMap<String, (String, Source)>,
+    errors: &'a mut Vec<FieldError>,
+}
+
+impl<'a> Validator<'a> {
+    pub(crate) fn new(
+        prefix: &str,
+        values: &'a BTreeMap<String, (String, Source)>,
+        errors: &'a mut Vec<FieldError>,
+    ) -> Self {
+        Self {
+            prefix: prefix.to_ascii_lowercase(),
+            values,
+            errors,
+        }
+    }
+
+    /// Record an error for `field` (name without the section prefix) unless `ok`.
+    pub fn check(&mut self, field: &str, ok: bool, expected: impl Into<String>) {
+        if !ok {
+            let key = format!("{}{field}", self.prefix);
+            self.errors
+                .push(field_error(&key, self.values, expected.into(), None));
+        }
+    }
+
+    /// `check` that `value` lies in `range`, e.g. `HumanDuration::from_millis(10)..=HumanDuration::from_secs(300)`.
+    pub fn check_range<T: PartialOrd + fmt::Display>(
+        &mut self,
+        field: &str,
+        value: &T,
+        range: RangeInclusive<T>,
+    ) {
+        let expected = format!("between {} and {}", range.start(), range.end());
+        self.check(field, range.contains(value), expected);
+    }
+}
+
+/// Error for a known key, with its raw value (redacted if secret) and source.
+pub(crate) fn field_error(
+    key: &str,
+    values: &BTreeMap<String, (String, Source)>,
+    expected: String,
+    hint: Option<String>,
+) -> FieldError {
+    let (value, source) = match values.get(key) {
+        Some((value, source)) => {
+            let value = if is_secret(key, source) {
+                REDACTED.to_string()
+            } else {
+                value.clone()
+            };
+            (Some(value), Some(source.clone()))
+        }
+        None => (None, None),
+    };
+
+    FieldError {
+        key: key.to_ascii_uppercase(),
+        value,
+        source,
+        expected,
+        hint,
+    }
+}
+
+pub(crate) fn is_secret(key: &str, source: &Source) -> bool {
+    matches!(source, Source::EnvFile(_))
+        || KEYS.iter().any(|k| k.secret && k.name == key)
+        || looks_secret(key)
+}
+
+/// Service keys whose name suggests a credential (`*_password`, `*_token`, ...).
+pub(crate) fn looks_secret(key: &str) -> bool {
+    SECRET_HINTS.iter().any(|hint| key.contains(hint))
+}
+
+/// Closest known key within a small edit distance (typos, transpositions).
+pub(crate) fn suggest<'k>(name: &str, known: &'k [String]) -> Option<&'k str> {
+    let name = name.to_ascii_lowercase();
+    known
+        .iter()
+        .map(|k| (distance(&name, k), k))
+        .filter(|(d, k)| *d > 0 && *d <= max_distance(k))
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k.as_str())
+}
+
+fn max_distance(key: &str) -> usize {
+    if key.len() < 8 { 1 } else { 2 }
+}
+
+/// Optimal string alignment distance (Levenshtein + adjacent transpositions).
+fn distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
@@ -0,0 +1,176 @@
+//! Config schema and dumps: every recognised variable, its default and its effective value.
+//!
+//! Built from the config types themselves (`KeySpec` docs + serde defaults), so the
+//! generated env reference and `--print-config` output cannot drift from the loader.
+
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use crate::{
+    AppConfig, DatabaseConfig, KEYS, ServiceConfig, ShipyardConfig, Source,
+    fields::struct_fields,
+    report::{REDACTED, is_secret, looks_secret},
+};
+
+/// One recognised env var.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarSchema {
+    /// Env var name, e.g. `SERVICE_PORT`
+    pub name: String,
+    /// Expected format, e.g. "TCP port in 1..=65535"
+    pub expected: String,
+    /// Value used when unset (`None`: unset means unset, or the var is required)
+    pub default: Option<String>,
+    /// Loading fails when unset
+    pub required: bool,
+    /// Redacted in error reports and config dumps
+    pub secret: bool,
+    pub description: String,
+}
+
+impl AppConfig {
+    /// Every platform variable, in `KEYS` order.
+    pub fn schema() -> Vec<VarSchema> {
+        // Parsed from nothing rather than `AppConfig::dev()`: loading builds this schema.
+        let (mut defaults, _) = section_defaults::<AppConfig>();
+        defaults.extend(section_defaults::<DatabaseConfig>().0);
+        KEYS.iter()
+            .map(|spec| VarSchema {
+                name: spec.name.to_ascii_uppercase(),
+                expected: spec.expected.to_string(),
+                default: defaults.get(spec.name).cloned(),
+                required: false,
+                secret: spec.secret,
+                description: spec.description.to_string(),
+            })
+            .collect()
+    }
+}
+
+impl<S: ServiceConfig> ShipyardConfig<S> {
+    /// Platform variables followed by the `<PREFIX>*` service section, in field order.
+    pub fn schema(prefix: &str) -> Vec<VarSchema> {
+        let prefix = crate::loader::normalize_prefix(prefix);
+        let (defaults, required) = section_defaults::<S>();
+
+        let mut vars = AppConfig::schema();
+        vars.extend(struct_fields::<S>().iter().map(|field| {
+            let spec = S::keys().iter().find(|k| k.name == *field);
+            let required = required.contains(field);
+            VarSchema {
+                name: format!("{prefix}{}", field.to_ascii_uppercase()),
+                expected: spec.map_or("", |k| k.expected).to_string(),
+                default: (!required).then(|| defaults.get(*field).cloned()).flatten(),
+                required,
+                secret: spec.is_some_and(|k| k.secret) || looks_secret(field),
+                description: spec.map_or("", |k| k.description).to_string(),
+            }
+        }));
+        vars
+    }
+}
+
+/// Markdown env reference for `vars`, titled `title`.
+pub fn render_markdown(title: &str, vars: &[VarSchema]) -> String {
+    let mut out = format!(
+        "# {title}\n\n\
+         <!-- Generated by `--print-config-docs`; do not edit by hand. -->\n\n\
+         Every variable can also be set in the config file (lowercase key) or read from a file\n\
+         via `<NAME>_FILE`. See [configuration.md](configuration.md) for layering and errors.\n\n\
+         | Variable | Expected | Default | Required | Secret | Description |\n\
+         |---|---|---|---|---|---|\n"
+    );
+    for var in vars {
+        let default = var
+            .default
+            .as_deref()
+            .map_or_else(|| "unset".to_string(), |d| format!("`{d}`"));
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | {} | {} | {} | {} |",
+            var.name,
+            cell(&var.expected),
+            default,
+            if var.required { "yes" } else { "no" },
+            if var.secret { "yes" } else { "no" },
+            cell(&var.description),
+        );
+    }
+    out
+}
+
+/// Effective values for a dump, in schema order; secrets and `*_FILE` values redacted.
+pub(crate) fn effective(
+    vars: &[VarSchema],
+    values: &BTreeMap<String, String>,
+    sources: &BTreeMap<String, Source>,
+) -> Vec<(String, Option<String>)> {
+    vars.iter()
+        .map(|var| {
+            let key = var.name.to_ascii_lowercase();
+            let secret = var.secret || sources.get(&key).is_some_and(|s| is_secret(&key, s));
+            let value = values.get(&key).map(|v| {
+                if secret {
+                    REDACTED.to_string()
+                } else {
+                    v.clone()
+                }
+            });
+            (key, value)
+        })
+        .collect()
+}
+
+/// `AppConfig` (with its database section) as `key → value`; unset options are omitted.
+pub(crate) fn app_values(config: &AppConfig) -> BTreeMap<String, String> {
+    let mut values = flatten("", config);
+    values.extend(flatten("", &config.database));
+    values
+}
+
+/// Top-level fields of `value` as `<prefix><field> → value`, skipping `null`s.
+pub(crate) fn flatten<T: Serialize>(prefix: &str, value: &T) -> BTreeMap<String, String> {
+    let Ok(serde_json::Value::Object(fields)) = serde_json::to_value(value) else {
+        return BTreeMap::new();
+    };
+    fields
+        .into_iter()
+        .filter_map(|(field, value)| {
+            let value = match value {
+                serde_json::Value::Null => return None,
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            };
+            Some((format!("{prefix}{field}"), value))
+        })
+        .collect()
+}
+
+/// Defaults of a section parsed from nothing, plus the fields that have none.
+fn section_defaults<S: DeserializeOwned + Serialize>()
+-> (BTreeMap<String, String>, Vec<&'static str>) {
+    let fields = struct_fields::<S>();
+    let mut required = Vec::new();
+    let mut pairs: Vec<(String, String)> = Vec::new();
+
+    for _ in 0..=fields.len() {
+        match envy::from_iter::<_, S>(pairs.clone()) {
+            Ok(section) => return (flatten("", &section), required),
+            Err(envy::Error::MissingValue(field)) => {
+                if let Some(field) = fields.iter().find(|f| **f == field) {
+                    required.push(*field);
+                }
+                // Placeholder so the next round surfaces the next required field.
+                pairs.push((field.to_ascii_uppercase(), "0".to_string()));
+            }
+            Err(envy::Error::Custom(_)) => break,
+        }
+    }
+    (BTreeMap::new(), required)
+}
+
+/// Keep table cells on one line.
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
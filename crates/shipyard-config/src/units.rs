@@ -0,0 +1,179 @@
+//! Human-friendly config values: `"250ms"`, `"30s"`, `"5m"`, `"10MiB"`.
+//!
+//! Both types parse from strings (env, file, overrides alike) and `Display` in
+//! the largest exact unit, so a config dump parses back to the same value.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+const DURATION_UNITS: &[(&str, u64)] = &[
+    ("d", 86_400_000),
+    ("h", 3_600_000),
+    ("m", 60_000),
+    ("s", 1_000),
+    ("ms", 1),
+];
+
+const DURATION_EXPECTED: &str = "a duration like 250ms, 30s, 5m, 1h or 1d";
+
+/// A duration with millisecond precision, written as `<integer><unit>`.
+///
+/// Units: `ms`, `s`, `m`, `h`, `d`. `"0"` is accepted without a unit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HumanDuration(Duration);
+
+impl HumanDuration {
+    pub const fn from_millis(ms: u64) -> Self {
+        Self(Duration::from_millis(ms))
+    }
+
+    pub const fn from_secs(secs: u64) -> Self {
+        Self(Duration::from_secs(secs))
+    }
+
+    pub const fn as_duration(self) -> Duration {
+        self.0
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+impl From<HumanDuration> for Duration {
+    fn from(d: HumanDuration) -> Self {
+        d.0
+    }
+}
+
+impl FromStr for HumanDuration {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let ms = parse_with_units(s, DURATION_UNITS).ok_or(DURATION_EXPECTED)?;
+        Ok(Self::from_millis(ms))
+    }
+}
+
+impl fmt::Display for HumanDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = u64::try_from(self.0.as_millis()).unwrap_or(u64::MAX);
+        write_with_units(f, ms, DURATION_UNITS, "s")
+    }
+}
+
+const BYTE_UNITS: &[(&str, u64)] = &[
+    ("TiB", 1 << 40),
+    ("GiB", 1 << 30),
+    ("MiB", 1 << 20),
+    ("KiB", 1 << 10),
+    ("TB", 1_000_000_000_000),
+    ("GB", 1_000_000_000),
+    ("MB", 1_000_000),
+    ("KB", 1_000),
+    ("B", 1),
+];
+
+const BYTES_EXPECTED: &str = "a size like 512B, 64KiB, 10MiB, 1GiB or 5MB";
+
+/// A byte count, written as `<integer><unit>`.
+///
+/// Units: `B`, binary `KiB`/`MiB`/`GiB`/`TiB` and decimal `KB`/`MB`/`GB`/`TB`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(u64);
+
+impl ByteSize {
+    pub const fn b(bytes: u64) -> Self {
+        Self(bytes)
+    }
+
+    pub const fn kib(n: u64) -> Self {
+        Self(n << 10)
+    }
+
+    pub const fn mib(n: u64) -> Self {
+        Self(n << 20)
+    }
+
+    pub const fn gib(n: u64) -> Self {
+        Self(n << 30)
+    }
+
+    pub const fn as_u64(self) -> u64 {
+        self.0
+    }
+
+    /// For APIs that take `usize` limits (e.g. body size); saturates on 32-bit targets.
+    pub fn as_usize(self) -> usize {
+        usize::try_from(self.0).unwrap_or(usize::MAX)
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_with_units(s, BYTE_UNITS)
+            .map(Self)
+            .ok_or_else(|| BYTES_EXPECTED.to_string())
+    }
+}
+
+impl fmt::Display for ByteSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_with_units(f, self.0, BYTE_UNITS, "B")
+    }
+}
+
+/// `<digits><unit>` (surrounding whitespace allowed); bare `0` needs no unit.
+fn parse_with_units(s: &str, units: &[(&str, u64)]) -> Option<u64> {
+    let s = s.trim();
+    if s == "0" {
+        return Some(0);
+    }
+
+    let split = s.find(|c: char| !c.is_ascii_digit())?;
+    let (digits, unit) = s.split_at(split);
+    let n: u64 = digits.parse().ok()?;
+    let factor = units.iter().find(|(u, _)| *u == unit.trim())?.1;
+    n.checked_mul(factor)
+}
+
+/// Largest unit that divides `n` exactly (units are listed largest first).
+fn write_with_units(
+    f: &mut fmt::Formatter<'_>,
+    n: u64,
+    units: &[(&str, u64)],
+    zero_unit: &str,
+) -> fmt::Result {
+    if n == 0 {
+        return write!(f, "0{zero_unit}");
+    }
+    let (unit, factor) = units
+        .iter()
+        .find(|(_, factor)| n.is_multiple_of(*factor))
+        .copied()
+        .unwrap_or(units[units.len() - 1]);
+    write!(f, "{}{unit}", n / factor)
+}
+
+macro_rules! serde_via_str {
+    ($ty:ty) => {
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let raw = String::deserialize(deserializer)?;
+                raw.parse().map_err(de::Error::custom)
+            }
+        }
+
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+    };
+}
+
+serde_via_str!(HumanDuration);
+serde_via_str!(ByteSize);
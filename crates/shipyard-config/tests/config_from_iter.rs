@@ -1,10 +1,16 @@
-use shipyard_config::{AppConfig, Environment};
+use std::time::Duration;
+
+use shipyard_config::{AppConfig, Environment, LogsExporter, MetricsExporter};
 
 #[test]
 fn defaults_load_when_env_is_empty() {
     let cfg = AppConfig::from_kv(std::iter::empty::<(&str, &str)>()).unwrap();
     assert_eq!(cfg.env, Environment::Dev);
     assert_eq!(cfg.service_port, 8080);
+    assert_eq!(cfg.shutdown_pre_stop_delay_secs, 5);
+    assert_eq!(cfg.shutdown_drain_timeout_secs, 25);
+    assert_eq!(cfg.otel_metrics_exporter, MetricsExporter::None);
+    assert_eq!(cfg.otel_logs_exporter, LogsExporter::None);
 }
 
 #[test]
@@ -13,3 +19,173 @@ fn invalid_port_fails_fast() {
     let msg = err.to_string();
     assert!(msg.contains("SERVICE_PORT"));
 }
+
+#[test]
+fn zero_drain_timeout_fails_fast() {
+    let err = AppConfig::from_kv([("SHUTDOWN_DRAIN_TIMEOUT_SECS", "0")]).unwrap_err();
+    assert!(err.to_string().contains("SHUTDOWN_DRAIN_TIMEOUT_SECS"));
+}
+
+#[test]
+fn otlp_metrics_need_an_endpoint_and_a_sane_interval() {
+    let cfg = AppConfig::from_kv([
+        ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://otelcol:4317"),
+        ("OTEL_METRICS_EXPORTER", "otlp"),
+        ("OTEL_METRIC_EXPORT_INTERVAL", "15s"),
+    ])
+    .unwrap();
+    assert_eq!(cfg.otel_metrics_exporter, MetricsExporter::Otlp);
+    assert_eq!(
+        cfg.otel_metric_export_interval.as_duration(),
+        Duration::from_secs(15)
+    );
+
+    let msg = AppConfig::from_kv([("OTEL_METRICS_EXPORTER", "otlp")])
+        .unwrap_err()
+        .to_string();
+    assert!(msg.contains("OTEL_METRICS_EXPORTER"), "{msg}");
+
+    let msg = AppConfig::from_kv([("OTEL_METRIC_EXPORT_INTERVAL", "100ms")])
+        .unwrap_err()
+        .to_string();
+    assert!(msg.contains("OTEL_METRIC_EXPORT_INTERVAL"), "{msg}");
+}
+
+#[test]
+fn otlp_logs_need_an_endpoint() {
+    let cfg = AppConfig::from_kv([
+        ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://otelcol:4317"),
+        ("OTEL_LOGS_EXPORTER", "otlp"),
+    ])
+    .unwrap();
+    assert_eq!(cfg.otel_logs_exporter, LogsExporter::Otlp);
+
+    let msg = AppConfig::from_kv([("OTEL_LOGS_EXPORTER", "otlp")])
+        .unwrap_err()
+        .to_string();
+    assert!(msg.contains("OTEL_LOGS_EXPORTER"), "{msg}");
+}
+
+#[test]
+fn service_name_overrides_the_binary_default() {
+    assert_eq!(
+        AppConfig::dev().service_name("fulfilment-api"),
+        "fulfilment-api"
+    );
+
+    let cfg = AppConfig::from_kv([("OTEL_SERVICE_NAME", "fulfilment-canary")]).unwrap();
+    assert_eq!(cfg.service_name("fulfilment-api"), "fulfilment-canary");
+
+    let msg = AppConfig::from_kv([("OTEL_SERVICE_NAME", " ")])
+        .unwrap_err()
+        .to_string();
+    assert!(msg.contains("OTEL_SERVICE_NAME"), "{msg}");
+}
+
+#[test]
+fn resource_attributes_are_parsed_and_validated() {
+    let cfg = AppConfig::from_kv([(
+        "OTEL_RESOURCE_ATTRIBUTES",
+        "team=fulfilment, region=eu-west-1,note=a%2Cb%3Dc,",
+    )])
+    .unwrap();
+    assert_eq!(
+        cfg.resource_attributes(),
+        vec![
+            ("team".to_string(), "fulfilment".to_string()),
+            ("region".to_string(), "eu-west-1".to_string()),
+            ("note".to_string(), "a,b=c".to_string()),
+        ]
+    );
+    assert!(AppConfig::dev().resource_attributes().is_empty());
+
+    for bad in ["team", "=x", "note=%zz"] {
+        let msg = AppConfig::from_kv([("OTEL_RESOURCE_ATTRIBUTES", bad)])
+            .unwrap_err()
+            .to_string();
+        assert!(msg.contains("OTEL_RESOURCE_ATTRIBUTES"), "{bad}: {msg}");
+    }
+}
+
+#[test]
+fn otlp_transport_settings_are_parsed_and_validated() {
+    use shipyard_config::{OtlpCompression, OtlpProtocol};
+
+    let cfg = AppConfig::from_kv([
+        (
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "https://otel.example.com:4317",
+        ),
+        (
+            "OTEL_EXPORTER_OTLP_HEADERS",
+            "authorization=Bearer%20t0ken,x-tenant=acme",
+        ),
+        ("OTEL_EXPORTER_OTLP_COMPRESSION", "gzip"),
+        ("OTEL_EXPORTER_OTLP_TIMEOUT", "3s"),
+        ("OTEL_EXPORTER_OTLP_CERTIFICATE", "/etc/ssl/otel-ca.pem"),
+    ])
+    .unwrap();
+    assert_eq!(cfg.otel_exporter_otlp_protocol, OtlpProtocol::Grpc);
+    assert_eq!(cfg.otel_exporter_otlp_compression, OtlpCompression::Gzip);
+    assert_eq!(
+        Duration::from(cfg.otel_exporter_otlp_timeout),
+        Duration::from_secs(3)
+    );
+    assert_eq!(
+        cfg.otlp_headers(),
+        vec![
+            ("authorization".to_string(), "Bearer t0ken".to_string()),
+            ("x-tenant".to_string(), "acme".to_string()),
+        ]
+    );
+    assert!(
+        !format!("{cfg:?}").contains("t0ken"),
+        "headers are redacted"
+    );
+
+    let cfg = AppConfig::from_kv([("OTEL_EXPORTER_OTLP_PROTOCOL", "http/protobuf")]).unwrap();
+    assert_eq!(cfg.otel_exporter_otlp_protocol, OtlpProtocol::HttpProtobuf);
+
+    for (key, value) in [
+        ("OTEL_EXPORTER_OTLP_PROTOCOL", "http/json"),
+        ("OTEL_EXPORTER_OTLP_HEADERS", "no-equals-sign"),
+        ("OTEL_EXPORTER_OTLP_TIMEOUT", "10"),
+        ("OTEL_EXPORTER_OTLP_TIMEOUT", "10m"),
+    ] {
+        let msg = AppConfig::from_kv([(key, value)]).unwrap_err().to_string();
+        assert!(msg.contains(key), "{key}={value}: {msg}");
+    }
+
+    let msg = AppConfig::from_kv([
+        ("OTEL_EXPORTER_OTLP_PROTOCOL", "http/protobuf"),
+        ("OTEL_EXPORTER_OTLP_COMPRESSION", "gzip"),
+    ])
+    .unwrap_err()
+    .to_string();
+    assert!(msg.contains("OTEL_EXPORTER_OTLP_COMPRESSION"), "{msg}");
+}
+
+#[test]
+fn log_format_defaults_by_environment() {
+    use shipyard_config::LogFormat;
+
+    assert_eq!(AppConfig::dev().log_format(), LogFormat::Pretty);
+    let test = AppConfig::from_kv([("ENV", "test")]).unwrap();
+    assert_eq!(test.log_format(), LogFormat::Json);
+
+    let cfg = AppConfig::from_kv([
+        ("LOG_FORMAT", "logfmt"),
+        ("LOG_SPAN_LIST", "true"),
+        ("LOG_TARGET", "false"),
+        ("LOG_FLATTEN_SPAN_FIELDS", "true"),
+    ])
+    .unwrap();
+    assert_eq!(cfg.log_format(), LogFormat::Logfmt);
+    assert!(cfg.log_span_list && !cfg.log_target && cfg.log_flatten_span_fields);
+    assert!(!cfg.log_file_line);
+
+    let msg = AppConfig::from_kv([("LOG_FORMAT", "xml")])
+        .unwrap_err()
+        .to_string();
+    assert!(msg.contains("LOG_FORMAT"), "{msg}");
+}
@@ -0,0 +1,60 @@
+use std::time::Duration;
+
+use shipyard_config::AppConfig;
+
+#[test]
+fn database_defaults_match_previous_hard_coded_values() {
+    let db = AppConfig::dev().database;
+
+    assert!(db.url.is_none());
+    assert_eq!(db.max_connections, 5);
+    assert_eq!(db.min_connections, 0);
+    assert_eq!(db.acquire_timeout(), Duration::from_secs(5));
+    assert_eq!(db.idle_timeout(), Some(Duration::from_secs(600)));
+    assert_eq!(db.max_lifetime(), Some(Duration::from_secs(1800)));
+    assert_eq!(db.statement_timeout(), None);
+}
+
+#[test]
+fn database_section_reads_db_env_vars() {
+    let cfg = AppConfig::from_kv([
+        ("DATABASE_URL", "postgres://app:pw@db/app"),
+        ("DB_MAX_CONNECTIONS", "20"),
+        ("DB_MIN_CONNECTIONS", "2"),
+        ("DB_IDLE_TIMEOUT_SECS", "0"),
+        ("DB_STATEMENT_TIMEOUT_MS", "1500"),
+        ("DB_APPLICATION_NAME", "fulfilment-api"),
+    ])
+    .unwrap();
+
+    assert_eq!(cfg.database.max_connections, 20);
+    assert_eq!(cfg.database.min_connections, 2);
+    assert_eq!(cfg.database.idle_timeout(), None);
+    assert_eq!(
+        cfg.database.statement_timeout(),
+        Some(Duration::from_millis(1500))
+    );
+    assert_eq!(
+        cfg.database.application_name.as_deref(),
+        Some("fulfilment-api")
+    );
+}
+
+#[test]
+fn invalid_pool_bounds_name_the_env_vars() {
+    let err = AppConfig::from_kv([("DB_MAX_CONNECTIONS", "0")]).unwrap_err();
+    assert!(err.to_string().contains("DB_MAX_CONNECTIONS"));
+
+    let err = AppConfig::from_kv([("DB_MIN_CONNECTIONS", "10"), ("DB_MAX_CONNECTIONS", "5")])
+        .unwrap_err();
+    assert!(err.to_string().contains("DB_MIN_CONNECTIONS"));
+
+    let err = AppConfig::from_kv([("DB_ACQUIRE_TIMEOUT_SECS", "0")]).unwrap_err();
+    assert!(err.to_string().contains("DB_ACQUIRE_TIMEOUT_SECS"));
+}
+
+#[test]
+fn missing_url_is_reported_when_required() {
+    let err = AppConfig::dev().database.require_url().unwrap_err();
+    assert!(err.to_string().contains("DATABASE_URL"));
+}
@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+
+use shipyard_config::{ConfigError, ConfigLoader, Source};
+
+/// Write `contents` to a per-test file under the system temp dir.
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shipyard-config-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn file_from_env_var_is_layered_under_env_and_overrides() {
+    let path = write_file(
+        "layers.toml",
+        "service_port = 9000\nmaintenance_retry_after_secs = 30\nenv = \"test\"\n",
+    );
+
+    let loaded = ConfigLoader::from_kv([
+        ("SHIPYARD_CONFIG_FILE", path.to_str().unwrap()),
+        ("MAINTENANCE_RETRY_AFTER_SECS", "45"),
+        ("ENV", "prod"),
+    ])
+    .set("env", "dev")
+    .load()
+    .unwrap();
+
+    assert_eq!(loaded.config.service_port, 9000);
+    assert_eq!(loaded.config.maintenance_retry_after_secs, 45);
+    assert_eq!(loaded.config.env, shipyard_config::Environment::Dev);
+
+    assert_eq!(loaded.source_of("service_port"), Some(&Source::File(path)));
+    assert_eq!(
+        loaded.source_of("maintenance_retry_after_secs"),
+        Some(&Source::Env)
+    );
+    assert_eq!(loaded.source_of("env"), Some(&Source::Override));
+    assert_eq!(
+        loaded.source_of("shutdown_drain_timeout_secs"),
+        Some(&Source::Default)
+    );
+}
+
+#[test]
+fn yaml_files_are_supported() {
+    let path = write_file(
+        "layers.yaml",
+        "service_port: 7000\nmaintenance_file: /tmp/m\n",
+    );
+
+    let cfg = ConfigLoader::from_kv(std::iter::empty::<(&str, &str)>())
+        .file(&path)
+        .load()
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(cfg.service_port, 7000);
+    assert_eq!(cfg.maintenance_file.as_deref(), Some("/tmp/m"));
+}
+
+#[test]
+fn validation_still_fails_fast_after_merge() {
+    let path = write_file("invalid.toml", "service_port = 9000\n");
+
+    let err = ConfigLoader::from_kv([("SHIPYARD_CONFIG_FILE", path.to_str().unwrap())])
+        .set("SERVICE_PORT", "0")
+        .load()
+        .unwrap_err();
+
+    assert!(err.to_string().contains("SERVICE_PORT"));
+}
+
+#[test]
+fn unknown_file_keys_are_rejected() {
+    let path = write_file("unknown.toml", "service_prot = 9000\n");
+
+    let err = ConfigLoader::from_kv(std::iter::empty::<(&str, &str)>())
+        .file(&path)
+        .load()
+        .unwrap_err();
+
+    assert!(matches!(err, ConfigError::Invalid(_)));
+    assert!(err.to_string().contains("service_prot"));
+}
+
+#[test]
+fn debug_output_shows_sources() {
+    let loaded = ConfigLoader::from_kv([("SERVICE_PORT", "8081")])
+        .load()
+        .unwrap();
+
+    let out = format!("{loaded:?}");
+    assert!(out.contains("\"service_port\": \"env\""));
+    assert!(out.contains("\"env\": \"default\""));
+}
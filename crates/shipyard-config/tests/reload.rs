@@ -0,0 +1,112 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU16, Ordering},
+    },
+    time::Duration,
+};
+
+use shipyard_config::{
+    AppConfig, CONFIG_FILE_ENV, ConfigLoader, ReloadOutcome, ReloadTriggers, Reloader,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+struct Settings {
+    log_filter: Option<String>,
+    port: u16,
+}
+
+impl From<&AppConfig> for Settings {
+    fn from(c: &AppConfig) -> Self {
+        Self {
+            log_filter: c.log_filter.clone(),
+            port: c.service_port,
+        }
+    }
+}
+
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shipyard-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+/// Loader whose `SERVICE_PORT` the test controls.
+fn port_loader(
+    port: Arc<AtomicU16>,
+) -> impl Fn() -> Result<Settings, shipyard_config::ConfigError> {
+    move || {
+        let port = port.load(Ordering::SeqCst).to_string();
+        AppConfig::from_kv([("SERVICE_PORT", port.as_str())]).map(|c| Settings::from(&c))
+    }
+}
+
+#[tokio::test]
+async fn reload_publishes_changed_settings() {
+    let port = Arc::new(AtomicU16::new(8080));
+    let initial = Settings::from(&AppConfig::dev());
+    let reloader = Reloader::new(initial, port_loader(port.clone()));
+    let mut rx = reloader.subscribe();
+
+    assert_eq!(reloader.reload(), ReloadOutcome::Unchanged);
+    assert!(!rx.has_changed().unwrap());
+
+    port.store(9000, Ordering::SeqCst);
+    assert_eq!(reloader.reload(), ReloadOutcome::Applied);
+    assert!(rx.has_changed().unwrap());
+    assert_eq!(rx.borrow_and_update().port, 9000);
+    assert_eq!(reloader.counts(), (1, 0));
+}
+
+#[tokio::test]
+async fn invalid_config_keeps_current_settings() {
+    let port = Arc::new(AtomicU16::new(9000));
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+    let seen = outcomes.clone();
+    let reloader = Reloader::new(Settings::from(&AppConfig::dev()), port_loader(port.clone()))
+        .observe(move |outcome| seen.lock().unwrap().push(outcome));
+    let rx = reloader.subscribe();
+
+    // Port 0 fails validation, exactly as it would at startup.
+    port.store(0, Ordering::SeqCst);
+    assert_eq!(reloader.reload(), ReloadOutcome::Failed);
+
+    assert_eq!(rx.borrow().port, 8080);
+    assert_eq!(reloader.counts(), (0, 1));
+    assert_eq!(*outcomes.lock().unwrap(), vec![ReloadOutcome::Failed]);
+}
+
+#[tokio::test]
+async fn file_change_triggers_reload() {
+    let path = write_file("live.toml", "log_filter = \"info\"\n");
+    let env = vec![(CONFIG_FILE_ENV.to_string(), path.display().to_string())];
+
+    let load = {
+        let env = env.clone();
+        move || {
+            ConfigLoader::from_kv(env.clone())
+                .load()
+                .map(|l| Settings::from(&l.config))
+        }
+    };
+    let reloader = Reloader::new(load().unwrap(), load);
+    let mut rx = reloader.subscribe();
+    let task = reloader.spawn(ReloadTriggers {
+        sighup: false,
+        file: Some(path.clone()),
+        file_poll_interval: Duration::from_millis(20),
+    });
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    std::fs::write(&path, "log_filter = \"debug\"\n").unwrap();
+
+    tokio::time::timeout(Duration::from_secs(5), rx.changed())
+        .await
+        .expect("reload not triggered")
+        .unwrap();
+    assert_eq!(rx.borrow().log_filter.as_deref(), Some("debug"));
+    task.abort();
+}
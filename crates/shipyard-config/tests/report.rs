@@ -0,0 +1,90 @@
+use shipyard_config::{AppConfig, ConfigError, FieldError};
+
+fn errors(kv: &[(&str, &str)]) -> Vec<FieldError> {
+    match AppConfig::from_kv(kv.iter().copied()).unwrap_err() {
+        ConfigError::Invalid(errors) => errors,
+        other => panic!("expected Invalid, got {other}"),
+    }
+}
+
+fn find<'a>(errors: &'a [FieldError], key: &str) -> &'a FieldError {
+    errors
+        .iter()
+        .find(|e| e.key == key)
+        .unwrap_or_else(|| panic!("no error for {key}: {errors:?}"))
+}
+
+#[test]
+fn all_errors_are_reported_at_once() {
+    let errors = errors(&[
+        ("SERVICE_PORT", "http"),
+        ("SHUTDOWN_DRAIN_TIMEOUT_SECS", "0"),
+        ("ENV", "staging"),
+        ("DB_MIN_CONNECTIONS", "9"),
+    ]);
+
+    assert_eq!(errors.len(), 4, "{errors:?}");
+
+    let port = find(&errors, "SERVICE_PORT");
+    assert_eq!(port.value.as_deref(), Some("http"));
+    assert_eq!(port.expected, "TCP port in 1..=65535");
+
+    assert_eq!(find(&errors, "ENV").expected, "one of dev, test, prod");
+    assert_eq!(
+        find(&errors, "SHUTDOWN_DRAIN_TIMEOUT_SECS")
+            .value
+            .as_deref(),
+        Some("0")
+    );
+    assert!(
+        find(&errors, "DB_MIN_CONNECTIONS")
+            .expected
+            .contains("DB_MAX_CONNECTIONS")
+    );
+}
+
+#[test]
+fn secret_values_are_redacted() {
+    let errors = errors(&[("DATABASE_URL", "  ")]);
+
+    let url = find(&errors, "DATABASE_URL");
+    assert_eq!(url.value.as_deref(), Some("[REDACTED]"));
+}
+
+#[test]
+fn near_miss_variables_get_suggestions() {
+    let errors = errors(&[("SERVICE_POTR", "9000"), ("DB_MAX_CONECTIONS", "3")]);
+
+    assert_eq!(
+        find(&errors, "SERVICE_POTR").hint.as_deref(),
+        Some("did you mean SERVICE_PORT?")
+    );
+    assert_eq!(
+        find(&errors, "DB_MAX_CONECTIONS").hint.as_deref(),
+        Some("did you mean DB_MAX_CONNECTIONS?")
+    );
+}
+
+#[test]
+fn unrelated_variables_are_ignored() {
+    let cfg = AppConfig::from_kv([
+        ("PATH", "/usr/bin"),
+        ("SERVICE_NAME", "x"),
+        ("OTEL_SERVICE_NAME", "fulfilment-api"),
+    ]);
+
+    assert!(cfg.is_ok());
+}
+
+#[test]
+fn display_lists_every_problem() {
+    let err = AppConfig::from_kv([("SERVICE_PORT", "0"), ("MAINTENANCE_FILE", " ")]).unwrap_err();
+    let msg = err.to_string();
+
+    assert!(msg.contains("2 problem(s)"), "{msg}");
+    assert!(
+        msg.contains("SERVICE_PORT=\"0\" (env): expected TCP port in 1..=65535"),
+        "{msg}"
+    );
+    assert!(msg.contains("MAINTENANCE_FILE"), "{msg}");
+}
@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use shipyard_config::{
+    AppConfig, ConfigLoader, KeySpec, ServiceConfig, ShipyardConfig,
+    cli::{ConfigFlag, config_flag},
+    render_markdown,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Payments {
+    provider_url: String,
+    #[serde(default = "default_retries")]
+    retries: u32,
+    #[serde(default)]
+    api_token: Option<String>,
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+const PAYMENTS_KEYS: &[KeySpec] = &[KeySpec::new(
+    "provider_url",
+    "https URL",
+    "Payment provider base URL",
+)];
+
+impl ServiceConfig for Payments {
+    fn keys() -> &'static [KeySpec] {
+        PAYMENTS_KEYS
+    }
+}
+
+fn var<'a>(vars: &'a [shipyard_config::VarSchema], name: &str) -> &'a shipyard_config::VarSchema {
+    vars.iter()
+        .find(|v| v.name == name)
+        .unwrap_or_else(|| panic!("{name} missing"))
+}
+
+#[test]
+fn platform_schema_lists_every_key_with_defaults() {
+    let vars = AppConfig::schema();
+
+    let port = var(&vars, "SERVICE_PORT");
+    assert_eq!(port.default.as_deref(), Some("8080"));
+    assert_eq!(port.expected, "TCP port in 1..=65535");
+    assert!(!port.required && !port.secret);
+    assert!(!port.description.is_empty());
+
+    let url = var(&vars, "DATABASE_URL");
+    assert!(url.secret);
+    assert_eq!(url.default, None);
+
+    assert_eq!(
+        var(&vars, "DB_MAX_CONNECTIONS").default.as_deref(),
+        Some("5")
+    );
+    assert_eq!(var(&vars, "ENV").default.as_deref(), Some("dev"));
+    assert!(vars.iter().all(|v| !v.description.is_empty()), "{vars:?}");
+}
+
+#[test]
+fn service_schema_marks_required_defaults_and_secrets() {
+    let vars = ShipyardConfig::<Payments>::schema("payments");
+
+    let url = var(&vars, "PAYMENTS_PROVIDER_URL");
+    assert!(url.required);
+    assert_eq!(url.default, None);
+    assert_eq!(url.description, "Payment provider base URL");
+
+    let retries = var(&vars, "PAYMENTS_RETRIES");
+    assert!(!retries.required);
+    assert_eq!(retries.default.as_deref(), Some("3"));
+
+    assert!(var(&vars, "PAYMENTS_API_TOKEN").secret);
+    assert_eq!(
+        vars.first().unwrap().name,
+        "ENV",
+        "platform vars come first"
+    );
+}
+
+#[test]
+fn render_prints_effective_values_with_sources_and_redacts_secrets() {
+    let loaded = ConfigLoader::from_kv([
+        ("DATABASE_URL", "postgres://app:hunter2@db/app"),
+        ("PAYMENTS_PROVIDER_URL", "https://pay"),
+        ("PAYMENTS_API_TOKEN", "tok_live_123"),
+    ])
+    .set("service_port", "9000")
+    .load_with::<Payments>("PAYMENTS")
+    .unwrap();
+
+    let out = loaded.render();
+    assert!(out.contains("SERVICE_PORT=9000  # override\n"), "{out}");
+    assert!(out.contains("DB_MAX_CONNECTIONS=5  # default\n"), "{out}");
+    assert!(out.contains("DATABASE_URL=[REDACTED]  # env\n"), "{out}");
+    assert!(
+        out.contains("PAYMENTS_API_TOKEN=[REDACTED]  # env\n"),
+        "{out}"
+    );
+    assert!(
+        out.contains("PAYMENTS_PROVIDER_URL=https://pay  # env\n"),
+        "{out}"
+    );
+    assert!(
+        out.contains("MAINTENANCE_FILE=  # default, unset\n"),
+        "{out}"
+    );
+    assert!(
+        !out.contains("hunter2") && !out.contains("tok_live"),
+        "{out}"
+    );
+}
+
+#[test]
+fn markdown_reference_has_one_row_per_var() {
+    let vars = ShipyardConfig::<Payments>::schema("PAYMENTS_");
+    let md = render_markdown("Config reference: payments", &vars);
+
+    assert!(md.starts_with("# Config reference: payments\n"));
+    assert!(md.contains("do not edit by hand"));
+    assert!(
+        md.contains("| `SERVICE_PORT` | TCP port in 1..=65535 | `8080` | no | no |"),
+        "{md}"
+    );
+    assert!(
+        md.contains("| `PAYMENTS_PROVIDER_URL` | https URL | unset | yes | no |"),
+        "{md}"
+    );
+    assert!(
+        md.contains("`off` \\| `read_only`"),
+        "pipes in cells are escaped"
+    );
+    assert_eq!(
+        md.lines().filter(|l| l.starts_with("| `")).count(),
+        vars.len()
+    );
+}
+
+#[test]
+fn config_flags_are_recognised() {
+    assert_eq!(
+        config_flag(["--print-config"]),
+        Some(ConfigFlag::PrintConfig)
+    );
+    assert_eq!(
+        config_flag(["--verbose", "--print-config-docs"]),
+        Some(ConfigFlag::PrintConfigDocs)
+    );
+    assert_eq!(config_flag(["serve"]), None);
+}
@@ -0,0 +1,70 @@
+use std::path::PathBuf;
+
+use shipyard_config::{AppConfig, ConfigLoader, Secret, Source};
+
+const URL: &str = "postgres://app:hunter2@db:5432/app";
+
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shipyard-secret-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn secret_is_redacted_everywhere() {
+    let secret = Secret::new(URL.to_string());
+
+    assert_eq!(format!("{secret:?}"), "[REDACTED]");
+    assert_eq!(secret.to_string(), "[REDACTED]");
+    assert_eq!(serde_json::to_string(&secret).unwrap(), "\"[REDACTED]\"");
+    assert_eq!(secret.expose(), URL);
+}
+
+#[test]
+fn config_debug_does_not_leak_database_url() {
+    let loaded = ConfigLoader::from_kv([("DATABASE_URL", URL)])
+        .load()
+        .unwrap();
+
+    assert_eq!(loaded.config.database.url.as_ref().unwrap().expose(), URL);
+    assert!(!format!("{loaded:?}").contains("hunter2"));
+    assert!(!format!("{:?}", loaded.config).contains("hunter2"));
+}
+
+#[test]
+fn file_suffix_reads_value_from_mounted_file() {
+    let path = write_file("database_url", &format!("{URL}\n"));
+
+    let loaded = ConfigLoader::from_kv([("DATABASE_URL_FILE", path.to_str().unwrap())])
+        .load()
+        .unwrap();
+
+    assert_eq!(loaded.config.database.url.as_ref().unwrap().expose(), URL);
+    assert_eq!(
+        loaded.source_of("database_url"),
+        Some(&Source::EnvFile(path))
+    );
+}
+
+#[test]
+fn value_and_file_together_are_rejected() {
+    let path = write_file("database_url_both", URL);
+
+    let err = AppConfig::from_kv([
+        ("DATABASE_URL", URL),
+        ("DATABASE_URL_FILE", path.to_str().unwrap()),
+    ])
+    .unwrap_err();
+
+    assert!(err.to_string().contains("DATABASE_URL_FILE"));
+}
+
+#[test]
+fn missing_secret_file_fails_fast() {
+    let err =
+        AppConfig::from_kv([("DATABASE_URL_FILE", "/nonexistent/shipyard/db-url")]).unwrap_err();
+
+    assert!(err.to_string().contains("/nonexistent/shipyard/db-url"));
+}
@@ -0,0 +1,89 @@
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use shipyard_config::{
+    ConfigError, ConfigLoader, ServiceConfig, ShipyardConfig, Source, Validator,
+};
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Billing {
+    api_base: String,
+    #[serde(default = "default_retries")]
+    retries: u32,
+}
+
+fn default_retries() -> u32 {
+    3
+}
+
+impl ServiceConfig for Billing {
+    fn validate(&self, v: &mut Validator<'_>) {
+        v.check("retries", self.retries <= 10, "integer <= 10");
+    }
+}
+
+fn write_file(name: &str, contents: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shipyard-service-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn platform_and_service_sections_load_together() {
+    let cfg = ShipyardConfig::<Billing>::from_kv(
+        "BILLING",
+        [
+            ("BILLING_API_BASE", "https://billing"),
+            ("SERVICE_PORT", "9001"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(cfg.app.service_port, 9001);
+    assert_eq!(cfg.service.api_base, "https://billing");
+    assert_eq!(cfg.service.retries, 3);
+}
+
+#[test]
+fn missing_required_service_field_names_the_env_var() {
+    let err = ShipyardConfig::<Billing>::from_kv("BILLING_", std::iter::empty::<(&str, &str)>())
+        .unwrap_err();
+
+    assert!(err.to_string().contains("BILLING_API_BASE"), "{err}");
+}
+
+#[test]
+fn service_validation_runs_like_platform_validation() {
+    let err = ShipyardConfig::<Billing>::from_kv(
+        "BILLING_",
+        [
+            ("BILLING_API_BASE", "https://billing"),
+            ("BILLING_RETRIES", "11"),
+        ],
+    )
+    .unwrap_err();
+
+    assert!(matches!(err, ConfigError::Invalid(_)));
+    assert!(err.to_string().contains("BILLING_RETRIES"));
+}
+
+#[test]
+fn service_keys_work_in_files_overrides_and_sources() {
+    let path = write_file("billing.toml", "billing_api_base = \"https://file\"\n");
+
+    let loaded = ConfigLoader::from_kv([("SHIPYARD_CONFIG_FILE", path.to_str().unwrap())])
+        .set("billing_retries", "5")
+        .load_with::<Billing>("BILLING_")
+        .unwrap();
+
+    assert_eq!(loaded.config.service.api_base, "https://file");
+    assert_eq!(loaded.config.service.retries, 5);
+    assert_eq!(
+        loaded.source_of("billing_api_base"),
+        Some(&Source::File(path))
+    );
+    assert_eq!(loaded.source_of("billing_retries"), Some(&Source::Override));
+    assert_eq!(loaded.source_of("service_port"), Some(&Source::Default));
+}
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use shipyard_config::{
+    ByteSize, ConfigError, ConfigLoader, HumanDuration, ServiceConfig, Validator,
+};
+
+#[test]
+fn duration_parses_units() {
+    let cases = [
+        ("250ms", Duration::from_millis(250)),
+        ("30s", Duration::from_secs(30)),
+        ("5m", Duration::from_secs(300)),
+        ("1h", Duration::from_secs(3600)),
+        ("2d", Duration::from_secs(172_800)),
+        ("0", Duration::ZERO),
+        (" 15s ", Duration::from_secs(15)),
+    ];
+    for (raw, expected) in cases {
+        let parsed: HumanDuration = raw.parse().unwrap();
+        assert_eq!(parsed.as_duration(), expected, "{raw}");
+    }
+
+    for bad in [
+        "",
+        "30",
+        "1.5s",
+        "-1s",
+        "5 minutes",
+        "s",
+        "99999999999999999999d",
+    ] {
+        assert!(bad.parse::<HumanDuration>().is_err(), "{bad:?} should fail");
+    }
+}
+
+#[test]
+fn byte_size_parses_binary_and_decimal_units() {
+    let cases = [
+        ("512B", 512),
+        ("64KiB", 64 * 1024),
+        ("10MiB", 10 * 1024 * 1024),
+        ("1GiB", 1 << 30),
+        ("5MB", 5_000_000),
+        ("0", 0),
+    ];
+    for (raw, expected) in cases {
+        let parsed: ByteSize = raw.parse().unwrap();
+        assert_eq!(parsed.as_u64(), expected, "{raw}");
+    }
+
+    for bad in ["10", "10mib", "1.5MiB", "MiB"] {
+        assert!(bad.parse::<ByteSize>().is_err(), "{bad:?} should fail");
+    }
+}
+
+#[test]
+fn display_uses_largest_exact_unit_and_round_trips() {
+    let durations = [
+        (HumanDuration::from_millis(250), "250ms"),
+        (HumanDuration::from_millis(1500), "1500ms"),
+        (HumanDuration::from_secs(120), "2m"),
+        (HumanDuration::from_secs(90), "90s"),
+        (HumanDuration::from_secs(0), "0s"),
+    ];
+    for (value, shown) in durations {
+        assert_eq!(value.to_string(), shown);
+        assert_eq!(shown.parse::<HumanDuration>().unwrap(), value);
+    }
+
+    let sizes = [
+        (ByteSize::mib(10), "10MiB"),
+        (ByteSize::b(5_000_000), "5MB"),
+        (ByteSize::b(1025), "1025B"),
+        (ByteSize::b(0), "0B"),
+    ];
+    for (value, shown) in sizes {
+        assert_eq!(value.to_string(), shown);
+        assert_eq!(shown.parse::<ByteSize>().unwrap(), value);
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Limits {
+    #[serde(default = "default_timeout")]
+    timeout: HumanDuration,
+    body_limit: ByteSize,
+}
+
+fn default_timeout() -> HumanDuration {
+    HumanDuration::from_secs(30)
+}
+
+impl ServiceConfig for Limits {
+    fn validate(&self, v: &mut Validator<'_>) {
+        v.check_range(
+            "timeout",
+            &self.timeout,
+            HumanDuration::from_secs(1)..=HumanDuration::from_secs(60),
+        );
+    }
+}
+
+#[test]
+fn units_deserialize_through_env_and_validate_ranges() {
+    let loaded = ConfigLoader::from_kv([("LIMITS_BODY_LIMIT", "10MiB")])
+        .load_with::<Limits>("LIMITS")
+        .unwrap();
+    assert_eq!(loaded.config.service.body_limit, ByteSize::mib(10));
+    assert_eq!(
+        loaded.config.service.timeout.as_duration(),
+        Duration::from_secs(30)
+    );
+
+    let err = ConfigLoader::from_kv([("LIMITS_BODY_LIMIT", "10 megs"), ("LIMITS_TIMEOUT", "5m")])
+        .load_with::<Limits>("LIMITS")
+        .unwrap_err();
+    let ConfigError::Invalid(errors) = err else {
+        panic!("expected Invalid, got {err:?}");
+    };
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors[0].key, "LIMITS_BODY_LIMIT");
+    assert!(
+        errors[0].expected.contains("10MiB"),
+        "{}",
+        errors[0].expected
+    );
+
+    let err = ConfigLoader::from_kv([("LIMITS_BODY_LIMIT", "1KiB"), ("LIMITS_TIMEOUT", "5m")])
+        .load_with::<Limits>("LIMITS")
+        .unwrap_err();
+    assert!(err.to_string().contains("LIMITS_TIMEOUT=\"5m\""), "{err}");
+    assert!(err.to_string().contains("between 1s and 1m"), "{err}");
+}
@@ -0,0 +1,254 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Instant,
+};
+
+use http::{HeaderMap, HeaderValue, Request, Response};
+use http_body::{Body, Frame, SizeHint};
+use opentelemetry::{global, propagation::Extractor, trace::TraceContextExt};
+use pin_project_lite::pin_project;
+use shipyard_web::{RequestId, TenantId};
+use tonic::Code;
+use tower::{Layer, Service};
+use tracing::{Instrument, Level, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::grpc_code_to_http;
+
+/// Apply the standard Shipyard contract to a tonic server.
+///
+/// ```ignore
+/// tonic::transport::Server::builder()
+///     .layer(shipyard_grpc::GrpcContractLayer)
+///     .add_service(MyServer::new(svc))
+/// ```
+///
+/// Contract (mirrors `shipyard_web::apply_web_contract`):
+/// - `x-request-id` metadata is reused or generated, and always returned
+/// - a valid `x-tenant-id` is exposed as a `TenantId` extension
+/// - every call has a `grpc.request` span carrying `request_id`, continuing inbound W3C context
+/// - exactly one `request.completed` log per call, with the same fields as HTTP, emitted once
+///   the response body has finished so the status comes from the trailers and the latency
+///   covers streaming
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GrpcContractLayer;
+
+impl<S> Layer<S> for GrpcContractLayer {
+    type Service = GrpcContract<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcContract { inner }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GrpcContract<S> {
+    inner: S,
+}
+
+type BoxFuture<T, E> = Pin<Box<dyn Future<Output = Result<T, E>> + Send>>;
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for GrpcContract<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response<CompletionBody<ResBody>>;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        // Take the service that was driven to readiness; leave a fresh clone behind.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let req_id = req
+            .headers()
+            .get("x-request-id")
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| RequestId(s.to_string()))
+            .unwrap_or_default();
+        req.extensions_mut().insert(req_id.clone());
+
+        let tenant = req
+            .headers()
+            .get("x-tenant-id")
+            .and_then(|v| v.to_str().ok())
+            .and_then(TenantId::parse);
+        if let Some(t) = &tenant {
+            req.extensions_mut().insert(t.clone());
+        }
+
+        let path = req.uri().path().to_string();
+        let span = tracing::info_span!(
+            "grpc.request",
+            request_id = %req_id.0,
+            method = %req.method(),
+            path = %path,
+            tenant_id = tenant.as_ref().map_or("", TenantId::as_str),
+        );
+        let parent =
+            global::get_text_map_propagator(|p| p.extract(&HeaderExtractor(req.headers())));
+        span.set_parent(parent);
+
+        let mut completion = Completion {
+            span: span.clone(),
+            req_id,
+            tenant,
+            method: req.method().as_str().to_string(),
+            path,
+            header_code: None,
+            start: Instant::now(),
+        };
+
+        Box::pin(
+            async move {
+                let mut res = inner.call(req).await?;
+
+                if let Ok(v) = HeaderValue::from_str(&completion.req_id.0) {
+                    res.headers_mut().insert("x-request-id", v);
+                }
+
+                // Errors are sent trailers-only (grpc-status in headers); success reports it
+                // in trailers, which only arrive at the end of the body.
+                completion.header_code = grpc_status(res.headers());
+                Ok(res.map(|inner| CompletionBody {
+                    inner,
+                    completion: Some(completion),
+                }))
+            }
+            .instrument(span),
+        )
+    }
+}
+
+pin_project! {
+    /// Response body of `GrpcContract`: logs `request.completed` when the call has really
+    /// finished (trailers received, body ended, or body dropped early by the client).
+    pub struct CompletionBody<B> {
+        #[pin]
+        inner: B,
+        completion: Option<Completion>,
+    }
+
+    impl<B> PinnedDrop for CompletionBody<B> {
+        fn drop(this: Pin<&mut Self>) {
+            if let Some(completion) = this.project().completion.take() {
+                let code = completion.header_code.unwrap_or(Code::Cancelled);
+                completion.finish(code);
+            }
+        }
+    }
+}
+
+impl<B: Body> Body for CompletionBody<B> {
+    type Data = B::Data;
+    type Error = B::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let frame = std::task::ready!(this.inner.poll_frame(cx));
+
+        // `Some(status)` once the call is over: trailers, a body error, or end of stream.
+        let finished = match &frame {
+            Some(Ok(frame)) => frame.trailers_ref().map(grpc_status),
+            Some(Err(_)) => Some(Some(Code::Unknown)),
+            None => Some(None),
+        };
+        if let Some(trailer_code) = finished
+            && let Some(completion) = this.completion.take()
+        {
+            let code = trailer_code.or(completion.header_code).unwrap_or(Code::Ok);
+            completion.finish(code);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Read the `RequestId` assigned by `GrpcContractLayer` inside a tonic handler.
+pub fn request_id<T>(req: &tonic::Request<T>) -> RequestId {
+    req.extensions()
+        .get::<RequestId>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+struct Completion {
+    span: Span,
+    req_id: RequestId,
+    tenant: Option<TenantId>,
+    method: String,
+    path: String,
+    header_code: Option<Code>,
+    start: Instant,
+}
+
+impl Completion {
+    fn finish(self, grpc_code: Code) {
+        let _entered = self.span.enter();
+
+        let cx = self.span.context();
+        let otel_span = cx.span();
+        let sc = otel_span.span_context();
+
+        let (trace_id, span_id) = if sc.is_valid() {
+            (sc.trace_id().to_string(), sc.span_id().to_string())
+        } else {
+            (String::new(), String::new())
+        };
+
+        tracing::event!(
+            Level::INFO,
+            request_id = %self.req_id.0,
+            tenant_id = self.tenant.as_ref().map_or("", TenantId::as_str),
+            trace_id = %trace_id,
+            span_id = %span_id,
+            method = %self.method,
+            path = %self.path,
+            status = grpc_code_to_http(grpc_code).as_u16(),
+            grpc_code = grpc_code as i32,
+            latency_us = self.start.elapsed().as_micros() as u64,
+            "request.completed"
+        );
+    }
+}
+
+fn grpc_status(headers: &HeaderMap) -> Option<Code> {
+    headers
+        .get("grpc-status")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<i32>().ok())
+        .map(Code::from)
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|k| k.as_str()).collect()
+    }
+}
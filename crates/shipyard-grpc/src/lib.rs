@@ -0,0 +1,19 @@
+//! shipyard-grpc
+//!
+//! The gRPC (tonic) counterpart of shipyard-web's contract pack.
+//!
+//! Provides:
+//! - Request correlation (`x-request-id` metadata) via `GrpcContractLayer`
+//! - W3C trace continuation (`traceparent` metadata → `grpc.request` span parent)
+//! - The same canonical `request.completed` log as HTTP services
+//! - `ApiError` → `tonic::Status` mapping with the request id in status details
+//!
+//! Non-goals:
+//! - Code generation / protobuf tooling (services own their protos)
+//! - Tracing/metrics export (belongs in shipyard-observability)
+
+pub mod contract;
+pub mod status;
+
+pub use contract::{CompletionBody, GrpcContract, GrpcContractLayer, request_id};
+pub use status::{grpc_code_to_http, status_from_api_error};
@@ -0,0 +1,72 @@
+use http::StatusCode;
+use shipyard_web::ApiError;
+use tonic::{Code, Status, metadata::MetadataValue};
+use tonic_types::{ErrorDetails, StatusExt};
+
+/// Map an `ApiError` to a `tonic::Status`.
+///
+/// - the gRPC code is derived from the stable `ApiError.code` (falling back to HTTP status)
+/// - status details are standard `google.rpc` messages, readable with `StatusExt`:
+///   `RequestInfo` carries the request id, `ErrorInfo.reason` the stable API code
+/// - `x-request-id` is also set on the status metadata
+pub fn status_from_api_error(err: ApiError) -> Status {
+    let code = grpc_code(err.code, err.status.as_u16());
+
+    let mut details = ErrorDetails::new();
+    details.set_request_info(err.request_id.clone(), "");
+    details.set_error_info(err.code, ERROR_DOMAIN, []);
+
+    let mut status = Status::with_error_details(code, err.message, details);
+    if let Ok(v) = MetadataValue::try_from(err.request_id.as_str()) {
+        status.metadata_mut().insert("x-request-id", v);
+    }
+    status
+}
+
+/// `ErrorInfo.domain` for errors raised through the Shipyard contract.
+const ERROR_DOMAIN: &str = "shipyard";
+
+fn grpc_code(api_code: &str, http_status: u16) -> Code {
+    match api_code {
+        "VALIDATION_ERROR" | "BAD_REQUEST" | "TENANT_REQUIRED" | "INVALID_TENANT" => {
+            Code::InvalidArgument
+        }
+        "NOT_FOUND" => Code::NotFound,
+        "CONFLICT" => Code::AlreadyExists,
+        "MAINTENANCE" => Code::Unavailable,
+        "INTERNAL_ERROR" => Code::Internal,
+        _ => match http_status {
+            400 => Code::InvalidArgument,
+            401 => Code::Unauthenticated,
+            403 => Code::PermissionDenied,
+            404 => Code::NotFound,
+            409 => Code::AlreadyExists,
+            429 => Code::ResourceExhausted,
+            501 => Code::Unimplemented,
+            503 => Code::Unavailable,
+            504 => Code::DeadlineExceeded,
+            _ => Code::Unknown,
+        },
+    }
+}
+
+/// HTTP-equivalent status for a gRPC code, so `request.completed` logs read the same
+/// across HTTP and gRPC services (dashboards filter on `status >= 500`).
+pub fn grpc_code_to_http(code: Code) -> StatusCode {
+    match code {
+        Code::Ok => StatusCode::OK,
+        Code::Cancelled => StatusCode::from_u16(499).unwrap_or(StatusCode::BAD_REQUEST),
+        Code::InvalidArgument | Code::OutOfRange | Code::FailedPrecondition => {
+            StatusCode::BAD_REQUEST
+        }
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::Unknown | Code::Internal | Code::DataLoss => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
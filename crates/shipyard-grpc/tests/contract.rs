@@ -0,0 +1,239 @@
+use std::convert::Infallible;
+
+use http::{HeaderMap, Request, Response, StatusCode};
+use http_body_util::{BodyExt, Full};
+use opentelemetry::global;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use shipyard_grpc::{CompletionBody, GrpcContractLayer, grpc_code_to_http, status_from_api_error};
+use shipyard_observability::testing;
+use shipyard_web::{ApiError, RequestId, TenantId};
+use tonic::Code;
+use tonic_types::StatusExt;
+use tower::{Layer, Service, ServiceExt, service_fn};
+
+/// Contract around a handler that answers synchronously with `respond`.
+fn contract<B, F>(
+    respond: F,
+) -> impl Service<Request<()>, Response = Response<CompletionBody<B>>, Error = Infallible>
+where
+    F: Fn(Request<()>) -> Response<B> + Clone + Send + 'static,
+    B: Send + 'static,
+{
+    GrpcContractLayer.layer(service_fn(move |req: Request<()>| {
+        let res = respond(req);
+        async move { Ok::<_, Infallible>(res) }
+    }))
+}
+
+fn svc()
+-> impl Service<Request<()>, Response = Response<CompletionBody<String>>, Error = Infallible> {
+    contract(|req: Request<()>| {
+        let req_id = req
+            .extensions()
+            .get::<RequestId>()
+            .map(|r| r.0.clone())
+            .unwrap_or_default();
+        Response::new(req_id)
+    })
+}
+
+fn grpc_status(code: Code) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert("grpc-status", (code as i32).into());
+    headers
+}
+
+fn req(req_id: Option<&str>) -> Request<()> {
+    let mut builder = Request::builder()
+        .method("POST")
+        .uri("/fulfilment.v1.Orders/Get");
+    if let Some(id) = req_id {
+        builder = builder.header("x-request-id", id);
+    }
+    builder.body(()).expect("build request")
+}
+
+#[tokio::test]
+async fn reuses_inbound_request_id_metadata() {
+    let res = svc().oneshot(req(Some("grpc-id-1"))).await.unwrap();
+
+    assert_eq!(res.headers()["x-request-id"], "grpc-id-1");
+    // Handlers see the same id through request extensions.
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "grpc-id-1");
+}
+
+#[tokio::test]
+async fn generates_request_id_when_missing() {
+    let res = svc().oneshot(req(None)).await.unwrap();
+
+    let header_id = res.headers()["x-request-id"].to_str().unwrap().to_string();
+    assert!(!header_id.trim().is_empty());
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, header_id.as_str());
+}
+
+#[tokio::test]
+async fn server_span_continues_inbound_traceparent() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    let telemetry = testing::capture();
+
+    let mut request = req(Some("grpc-trace"));
+    request.headers_mut().insert(
+        "traceparent",
+        "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+            .parse()
+            .unwrap(),
+    );
+    let res = svc().oneshot(request).await.unwrap();
+    res.into_body().collect().await.unwrap();
+
+    let span = telemetry
+        .spans()
+        .named("grpc.request")
+        .with_field("request_id", "grpc-trace")
+        .one();
+    assert_eq!(
+        span.trace_id.as_deref(),
+        Some("4bf92f3577b34da6a3ce929d0e0e4736")
+    );
+
+    let log = telemetry
+        .logs()
+        .message("request.completed")
+        .in_span("grpc.request")
+        .one();
+    assert_eq!(log.field("trace_id"), span.trace_id.as_deref());
+    assert_eq!(log.field("span_id"), span.span_id.as_deref());
+}
+
+#[tokio::test]
+async fn completed_log_takes_status_from_trailers_after_the_body() {
+    let telemetry = testing::capture();
+    let svc = contract(|_| {
+        Response::new(
+            Full::new(&b"partial"[..])
+                .with_trailers(async { Some(Ok(grpc_status(Code::NotFound))) }),
+        )
+    });
+
+    let res = svc.oneshot(req(Some("grpc-trailers"))).await.unwrap();
+    // Nothing is logged until the trailers have been sent.
+    assert!(!telemetry.logs().message("request.completed").exists());
+
+    res.into_body().collect().await.unwrap();
+    let log = telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("request_id", "grpc-trailers")
+        .in_span("grpc.request")
+        .one();
+    assert_eq!(log.field("status"), Some("404"));
+    assert_eq!(log.field("grpc_code"), Some("5"));
+    assert!(log.field("latency_us").is_some());
+}
+
+#[tokio::test]
+async fn completed_log_reads_trailers_only_errors_from_headers() {
+    let telemetry = testing::capture();
+    let svc = contract(|_| {
+        let mut res = Response::new(String::new());
+        *res.headers_mut() = grpc_status(Code::Unavailable);
+        res
+    });
+
+    let res = svc.oneshot(req(Some("grpc-unavailable"))).await.unwrap();
+    res.into_body().collect().await.unwrap();
+
+    telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("request_id", "grpc-unavailable")
+        .with_field("status", 503)
+        .with_field("grpc_code", Code::Unavailable as i32)
+        .one();
+}
+
+#[tokio::test]
+async fn tenant_metadata_reaches_handler_span_and_log() {
+    let telemetry = testing::capture();
+    let svc = contract(|req: Request<()>| {
+        let tenant = req.extensions().get::<TenantId>().cloned();
+        Response::new(tenant.map(|t| t.as_str().to_string()).unwrap_or_default())
+    });
+
+    let mut request = req(Some("grpc-tenant"));
+    request
+        .headers_mut()
+        .insert("x-tenant-id", "acme".parse().unwrap());
+    let res = svc.oneshot(request).await.unwrap();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "acme");
+
+    telemetry
+        .spans()
+        .named("grpc.request")
+        .with_field("tenant_id", "acme")
+        .one();
+    telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("tenant_id", "acme")
+        .with_field("status", 200)
+        .one();
+}
+
+#[test]
+fn api_error_maps_to_status_with_request_id_details() {
+    let req_id = RequestId("rid-42".to_string());
+    let status = status_from_api_error(ApiError::validation(&req_id, "items must not be empty"));
+
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "items must not be empty");
+    assert_eq!(status.metadata().get("x-request-id").unwrap(), "rid-42");
+
+    let details = status.get_error_details();
+    assert_eq!(details.request_info().unwrap().request_id, "rid-42");
+    assert_eq!(details.error_info().unwrap().reason, "VALIDATION_ERROR");
+}
+
+#[test]
+fn api_error_codes_map_to_grpc_codes() {
+    let req_id = RequestId("rid".to_string());
+
+    assert_eq!(
+        status_from_api_error(ApiError::not_found(&req_id)).code(),
+        Code::NotFound
+    );
+    assert_eq!(
+        status_from_api_error(ApiError::conflict(&req_id, "dup")).code(),
+        Code::AlreadyExists
+    );
+    assert_eq!(
+        status_from_api_error(ApiError::internal(&req_id)).code(),
+        Code::Internal
+    );
+    assert_eq!(
+        status_from_api_error(ApiError::from_status(
+            &req_id,
+            StatusCode::SERVICE_UNAVAILABLE
+        ))
+        .code(),
+        Code::Unavailable
+    );
+    // A bare 409 reads the same as the CONFLICT code.
+    assert_eq!(
+        status_from_api_error(ApiError::from_status(&req_id, StatusCode::CONFLICT)).code(),
+        Code::AlreadyExists
+    );
+}
+
+#[test]
+fn grpc_codes_map_back_to_http_for_logs() {
+    assert_eq!(grpc_code_to_http(Code::Ok), StatusCode::OK);
+    assert_eq!(grpc_code_to_http(Code::NotFound), StatusCode::NOT_FOUND);
+    assert_eq!(
+        grpc_code_to_http(Code::Internal),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use crate::exporter::Signal;
+
+#[derive(Debug, Error)]
+pub enum ObservabilityError {
+    #[error("invalid log filter `{filter}`: {source}")]
+    LogFilter {
+        filter: String,
+        #[source]
+        source: tracing_subscriber::filter::ParseError,
+    },
+
+    #[error("cannot set up OTLP {signal} export: {reason}")]
+    Exporter { signal: Signal, reason: String },
+
+    #[error("a global tracing subscriber is already installed")]
+    AlreadyInitialised(#[from] tracing_subscriber::util::TryInitError),
+}
+
+impl ObservabilityError {
+    pub(crate) fn exporter(signal: Signal) -> impl FnOnce(String) -> Self {
+        move |reason| Self::Exporter { signal, reason }
+    }
+}
@@ -0,0 +1,198 @@
+//! OTLP transport settings shared by the trace, metric and log exporters.
+//!
+//! - `Grpc` (tonic, port 4317): `endpoint` is used as is.
+//! - `HttpProtobuf` (reqwest, port 4318): the signal path (`/v1/traces`, ...) is
+//!   appended to `endpoint`, as the OTel spec does for `OTEL_EXPORTER_OTLP_ENDPOINT`.
+//!
+//! `https` endpoints trust the system roots plus `ca_file` when set.
+
+use std::{fmt, path::PathBuf, time::Duration};
+
+use crate::ObservabilityError;
+use opentelemetry_otlp::{HttpExporterBuilder, TonicExporterBuilder, WithExportConfig};
+use tonic::{
+    metadata::{AsciiMetadataKey, AsciiMetadataValue},
+    transport::{Certificate, ClientTlsConfig},
+};
+
+/// Default export timeout (the OTel default).
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Protocol {
+    /// OTLP over gRPC
+    #[default]
+    Grpc,
+    /// OTLP over HTTP with protobuf bodies
+    HttpProtobuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gRPC only; the HTTP exporter does not compress
+    Gzip,
+}
+
+#[derive(Clone)]
+pub struct ExporterConfig {
+    pub protocol: Protocol,
+
+    /// Sent with every export request, e.g. `("authorization", "Bearer ...")`
+    pub headers: Vec<(String, String)>,
+
+    pub compression: Option<Compression>,
+
+    /// Per export request
+    pub timeout: Duration,
+
+    /// PEM file with the CA that signed the collector's certificate
+    pub ca_file: Option<PathBuf>,
+}
+
+impl Default for ExporterConfig {
+    fn default() -> Self {
+        Self {
+            protocol: Protocol::default(),
+            headers: Vec::new(),
+            compression: None,
+            timeout: DEFAULT_TIMEOUT,
+            ca_file: None,
+        }
+    }
+}
+
+impl fmt::Debug for ExporterConfig {
+    /// Header values usually carry credentials: only names are printed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let header_names: Vec<&str> = self.headers.iter().map(|(k, _)| k.as_str()).collect();
+        f.debug_struct("ExporterConfig")
+            .field("protocol", &self.protocol)
+            .field("headers", &header_names)
+            .field("compression", &self.compression)
+            .field("timeout", &self.timeout)
+            .field("ca_file", &self.ca_file)
+            .finish()
+    }
+}
+
+/// What an exporter sends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Signal {
+    Traces,
+    Metrics,
+    Logs,
+}
+
+impl fmt::Display for Signal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Signal::Traces => "traces",
+            Signal::Metrics => "metrics",
+            Signal::Logs => "logs",
+        })
+    }
+}
+
+impl Signal {
+    fn http_path(self) -> &'static str {
+        match self {
+            Signal::Traces => "v1/traces",
+            Signal::Metrics => "v1/metrics",
+            Signal::Logs => "v1/logs",
+        }
+    }
+}
+
+impl ExporterConfig {
+    /// Exporter builder for `signal` (`SpanExporterBuilder`, `LogExporterBuilder`, ...).
+    pub(crate) fn builder<B>(&self, endpoint: &str, signal: Signal) -> Result<B, ObservabilityError>
+    where
+        B: From<TonicExporterBuilder> + From<HttpExporterBuilder>,
+    {
+        match self.protocol {
+            Protocol::Grpc => self.tonic(endpoint).map(B::from),
+            Protocol::HttpProtobuf => self.http(endpoint, signal).map(B::from),
+        }
+        .map_err(ObservabilityError::exporter(signal))
+    }
+
+    // The interceptor signature (`Result<_, tonic::Status>`) is fixed by tonic.
+    #[allow(clippy::result_large_err)]
+    fn tonic(&self, endpoint: &str) -> Result<TonicExporterBuilder, String> {
+        let mut builder = opentelemetry_otlp::new_exporter()
+            .tonic()
+            .with_endpoint(endpoint)
+            .with_timeout(self.timeout);
+
+        if endpoint.starts_with("https://") {
+            let mut tls = ClientTlsConfig::new();
+            if let Some(ca) = self.ca_pem()? {
+                tls = tls.ca_certificate(Certificate::from_pem(ca));
+            }
+            builder = builder.with_tls_config(tls);
+        }
+        if let Some(Compression::Gzip) = self.compression {
+            builder = builder.with_compression(opentelemetry_otlp::Compression::Gzip);
+        }
+        if !self.headers.is_empty() {
+            // An interceptor rather than `with_metadata`: it runs last and replaces the
+            // values the exporter read from `OTEL_EXPORTER_OTLP_HEADERS` instead of duplicating them.
+            let headers = self
+                .headers
+                .iter()
+                .map(|(k, v)| {
+                    let key = AsciiMetadataKey::from_bytes(k.to_ascii_lowercase().as_bytes())
+                        .map_err(|_| format!("invalid OTLP header name `{k}`"))?;
+                    let value = AsciiMetadataValue::try_from(v.as_str())
+                        .map_err(|_| format!("invalid value for OTLP header `{k}`"))?;
+                    Ok((key, value))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            builder = builder.with_interceptor(move |mut req: tonic::Request<()>| {
+                for (key, value) in &headers {
+                    req.metadata_mut().insert(key.clone(), value.clone());
+                }
+                Ok(req)
+            });
+        }
+        Ok(builder)
+    }
+
+    fn http(&self, endpoint: &str, signal: Signal) -> Result<HttpExporterBuilder, String> {
+        if self.compression.is_some() {
+            return Err("OTLP compression needs the grpc protocol".to_string());
+        }
+
+        // The exporter does not apply its timeout to HTTP requests; the client does.
+        let mut client = reqwest::Client::builder().timeout(self.timeout);
+        if let Some(ca) = self.ca_pem()? {
+            let ca = reqwest::Certificate::from_pem(&ca).map_err(|e| format!("OTLP CA: {e}"))?;
+            client = client.add_root_certificate(ca);
+        }
+        let client = client
+            .build()
+            .map_err(|e| format!("OTLP HTTP client: {e}"))?;
+
+        Ok(opentelemetry_otlp::new_exporter()
+            .http()
+            .with_http_client(client)
+            .with_protocol(opentelemetry_otlp::Protocol::HttpBinary)
+            .with_endpoint(format!(
+                "{}/{}",
+                endpoint.trim_end_matches('/'),
+                signal.http_path()
+            ))
+            .with_timeout(self.timeout)
+            .with_headers(self.headers.iter().cloned().collect()))
+    }
+
+    fn ca_pem(&self) -> Result<Option<Vec<u8>>, String> {
+        self.ca_file
+            .as_ref()
+            .map(|path| {
+                std::fs::read(path)
+                    .map_err(|e| format!("cannot read OTLP CA file {}: {e}", path.display()))
+            })
+            .transpose()
+    }
+}
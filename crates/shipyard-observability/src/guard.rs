@@ -0,0 +1,108 @@
+//! Flushes and shuts down the exporters installed by `init`.
+
+use std::{sync::mpsc, thread, time::Duration};
+
+use opentelemetry::global;
+use opentelemetry_sdk::{logs::LoggerProvider, metrics::SdkMeterProvider, trace::TracerProvider};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+/// Default bound on flushing exporters at shutdown.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Returned by `init`; keep it alive for the life of the process.
+///
+/// Dropping it (or calling `shutdown`) flushes pending spans, metrics and log records
+/// and shuts the exporters down, giving up after `shutdown_timeout` so an unreachable
+/// collector cannot hold up process exit. Stdout logging stays installed.
+///
+/// The exporters run on the Tokio runtime, so drop the guard on a multi-thread runtime
+/// (`#[tokio::main]`'s default) or after the runtime is gone. On a current-thread
+/// runtime the export tasks cannot run while the drop waits, so it does not wait and
+/// pending telemetry is dropped.
+#[must_use = "dropping the guard shuts down OTLP export"]
+#[derive(Debug)]
+pub struct ObservabilityGuard {
+    providers: Option<Providers>,
+    timeout: Duration,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Providers {
+    pub(crate) tracer: Option<TracerProvider>,
+    pub(crate) meter: Option<SdkMeterProvider>,
+    pub(crate) logger: Option<LoggerProvider>,
+}
+
+impl ObservabilityGuard {
+    pub(crate) fn new(providers: Providers, timeout: Duration) -> Self {
+        Self {
+            providers: Some(providers),
+            timeout,
+        }
+    }
+
+    /// Flush and shut down now (same as dropping the guard).
+    pub fn shutdown(self) {}
+}
+
+impl Drop for ObservabilityGuard {
+    fn drop(&mut self) {
+        let Some(providers) = self.providers.take() else {
+            return;
+        };
+        if providers.tracer.is_none() && providers.meter.is_none() && providers.logger.is_none() {
+            return;
+        }
+
+        // Exporter shutdown blocks until the batch tasks drain; run it aside to bound the wait.
+        let (done_tx, done_rx) = mpsc::channel();
+        thread::spawn(move || {
+            providers.shutdown();
+            let _ = done_tx.send(());
+        });
+        let wait = || {
+            if done_rx.recv_timeout(self.timeout).is_err() {
+                // The subscriber may already be gone at this point: stderr is the only safe channel.
+                eprintln!(
+                    "observability: exporters not flushed within {:?}; pending telemetry dropped",
+                    self.timeout
+                );
+            }
+        };
+        match Handle::try_current().map(|handle| handle.runtime_flavor()) {
+            // Let the other workers run the export tasks while this one waits.
+            Ok(RuntimeFlavor::MultiThread) => tokio::task::block_in_place(wait),
+            // Waiting would block the only thread the export tasks can run on.
+            Ok(_) => eprintln!(
+                "observability: guard dropped on a current-thread runtime; pending telemetry dropped"
+            ),
+            Err(_) => wait(),
+        }
+    }
+}
+
+impl Providers {
+    fn shutdown(self) {
+        if let Some(tracer) = self.tracer {
+            for result in tracer.force_flush() {
+                if let Err(err) = result {
+                    eprintln!("observability: trace flush failed: {err}");
+                }
+            }
+            // Tracers only hold weak references: dropping the last provider shuts down its
+            // processors. The global one goes first.
+            global::shutdown_tracer_provider();
+            drop(tracer);
+        }
+        if let Some(logger) = self.logger
+            && let Err(err) = logger.shutdown()
+        {
+            eprintln!("observability: logs shutdown failed: {err}");
+        }
+        if let Some(meter) = self.meter
+            && let Err(err) = meter.shutdown()
+        {
+            eprintln!("observability: metrics shutdown failed: {err}");
+        }
+    }
+}
@@ -1,85 +1,249 @@
 //! shipyard-observability
 //!
 //! Provides a golden-path observability initialisation:
-//! - JSON structured logs to stdout (tracing-subscriber)
-//! - OTLP trace export when endpoint is configured
+//! - Structured logs to stdout (tracing-subscriber): JSON, pretty, compact or logfmt
+//! - OTLP trace export when endpoint is configured, with configurable sampling
+//! - OTLP over gRPC or HTTP/protobuf, with headers, compression, timeout and a custom CA
+//! - OTLP metric export (optional), bridged from the service's Prometheus registry
+//! - OTLP log export (optional), correlated with traces
 //! - W3C trace context propagation
+//! - A guard that flushes and shuts down exporters (bounded) when dropped
+//! - In-memory span and log capture for tests (`testing` feature)
 //!
 //! Non-goals:
-//! - Metrics exporter (handled separately)
+//! - Replacing `/metrics` scraping (services keep their `prometheus-client` registry)
 //! - Vendor-specific logging backends
 
-use opentelemetry::global;
-use opentelemetry_otlp::WithExportConfig;
+use exporter::Signal;
+use guard::Providers;
+use opentelemetry::{global, trace::TracerProvider as _};
+use opentelemetry_otlp::{LogExporterBuilder, MetricsExporterBuilder, SpanExporterBuilder};
+use opentelemetry_sdk::logs::{BatchConfig, LoggerProvider};
+use opentelemetry_sdk::metrics::{
+    PeriodicReader, SdkMeterProvider,
+    reader::{DefaultAggregationSelector, DefaultTemporalitySelector},
+};
 use opentelemetry_sdk::propagation::TraceContextPropagator;
-use opentelemetry_sdk::trace as sdktrace;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use opentelemetry_sdk::{
+    Resource,
+    trace::{self as sdktrace, BatchSpanProcessor, TracerProvider},
+};
+use std::{sync::OnceLock, time::Duration};
+
+use tracing_subscriber::{
+    EnvFilter, Registry, layer::SubscriberExt, reload, util::SubscriberInitExt,
+};
+
+/// Set by `init`; lets `set_log_filter` swap the filter at runtime.
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+mod error;
+pub mod exporter;
+mod guard;
+pub mod logs;
+pub mod metrics;
+pub mod output;
+mod resource;
+pub mod sampling;
+#[cfg(feature = "testing")]
+pub mod testing;
+
+pub use error::ObservabilityError;
+pub use exporter::ExporterConfig;
+pub use guard::{DEFAULT_SHUTDOWN_TIMEOUT, ObservabilityGuard};
+pub use metrics::{MetricsConfig, PrometheusBridge, PrometheusSource};
+pub use output::{LogFormat, LogOutput};
+pub use resource::resource;
+pub use sampling::{Sampler, SamplingConfig};
 
 #[derive(Clone, Debug)]
 pub struct ObservabilityConfig {
     /// Logical service name (shown in traces).
     pub service_name: String,
 
+    /// `service.version`, e.g. `env!("CARGO_PKG_VERSION")`.
+    pub service_version: Option<String>,
+
+    /// `deployment.environment`, e.g. `dev`, `test`, `prod`.
+    pub deployment_environment: Option<String>,
+
+    /// Extra resource attributes (`OTEL_RESOURCE_ATTRIBUTES`); override detected and configured ones.
+    pub resource_attributes: Vec<(String, String)>,
+
     /// OTLP endpoint (e.g. http://otelcol:4317). If None, traces are not exported.
     pub otlp_endpoint: Option<String>,
 
+    /// Transport for everything sent to `otlp_endpoint` (default: gRPC, 10s timeout).
+    pub exporter: ExporterConfig,
+
+    /// Stdout log format and fields (default: JSON with the current span).
+    pub log_output: LogOutput,
+
     /// Log filter string (RUST_LOG compatible).
     /// Example: "info,fulfilment_api=debug,shipyard_web=debug"
     pub log_filter: Option<String>,
-}
 
-pub fn init(cfg: ObservabilityConfig) {
-    // W3C Trace Context propagation (traceparent/tracestate)
-    global::set_text_map_propagator(TraceContextPropagator::new());
+    /// OTLP metric export to `otlp_endpoint`. If None (or no endpoint), metrics are only scraped.
+    pub metrics: Option<MetricsConfig>,
+
+    /// Which traces are exported (default: parent-based, always on).
+    pub sampling: SamplingConfig,
 
-    // Logs (always on): JSON to stdout
+    /// Also ship log events to `otlp_endpoint` as OTLP logs (stdout logs stay on).
+    pub export_logs: bool,
+
+    /// Bound on flushing exporters when the guard is dropped (`DEFAULT_SHUTDOWN_TIMEOUT`).
+    pub shutdown_timeout: Duration,
+}
+
+/// Install the global subscriber (stdout logs, plus OTLP export when `otlp_endpoint` is set).
+///
+/// Nothing global is changed when this fails. Keep the returned guard until exit: dropping
+/// it flushes and shuts down the exporters.
+pub fn init(cfg: ObservabilityConfig) -> Result<ObservabilityGuard, ObservabilityError> {
+    // Logs (always on): stdout in the configured format
     let filter = cfg
         .log_filter
         .clone()
         .or_else(|| std::env::var("RUST_LOG").ok())
         .unwrap_or_else(|| "info".to_string());
+    let env_filter =
+        EnvFilter::try_new(&filter).map_err(|source| ObservabilityError::LogFilter {
+            filter: filter.clone(),
+            source,
+        })?;
+
+    let (filter_layer, filter_handle) = reload::Layer::new(env_filter);
+    let registry = tracing_subscriber::registry().with(filter_layer);
+    let fmt_layer = output::layer(&cfg.log_output, std::io::stdout);
+
+    // One resource for traces, metrics and logs
+    let resource = resource(&cfg);
+    let mut providers = Providers::default();
+
+    // Traces, metrics and logs: only wire exporters if endpoint is provided
+    let mut otel_layer = None;
+    let mut log_layer = None;
+    if let Some(endpoint) = &cfg.otlp_endpoint {
+        if let Some(metrics) = cfg.metrics {
+            providers.meter = Some(init_metrics(
+                endpoint,
+                &cfg.exporter,
+                resource.clone(),
+                metrics,
+            )?);
+        }
 
-    let fmt_layer = tracing_subscriber::fmt::layer()
-        .json()
-        .with_current_span(true);
-    // .with_span_list(true); // drop for less noise
-
-    let registry = tracing_subscriber::registry().with(tracing_subscriber::EnvFilter::new(filter));
-
-    let warn_init = |err: tracing_subscriber::util::TryInitError| {
-        tracing::warn!(error = %err, "observability already initialised or failed to initialise");
-        // eprintln!("observability init skipped or failed: {err}");
-    };
-
-    // Traces: only wire exporter if endpoint is provided
-    if let Some(endpoint) = cfg.otlp_endpoint {
-        let tracer = opentelemetry_otlp::new_pipeline()
-            .tracing()
-            .with_trace_config(
-                sdktrace::config().with_resource(opentelemetry_sdk::Resource::new(vec![
-                    opentelemetry::KeyValue::new("service.name", cfg.service_name.clone()),
-                ])),
-            )
-            .with_exporter(
-                opentelemetry_otlp::new_exporter()
-                    .tonic()
-                    .with_endpoint(endpoint),
-            )
-            .install_batch(opentelemetry_sdk::runtime::Tokio)
-            .expect("failed to init OTLP pipeline");
-
-        let otel_layer = tracing_opentelemetry::layer().with_tracer(tracer);
-
-        // Use try_init to avoid panics if init is called twice (tests / multiple binaries).
-        if let Err(err) = registry.with(fmt_layer).with(otel_layer).try_init() {
-            warn_init(err);
+        if cfg.export_logs {
+            let provider = init_logs(endpoint, &cfg.exporter, resource.clone())?;
+            log_layer = Some(logs::layer(&provider));
+            providers.logger = Some(provider);
         }
-    } else if let Err(err) = registry.with(fmt_layer).try_init() {
-        warn_init(err);
+
+        let provider = init_traces(endpoint, &cfg.exporter, resource, &cfg.sampling)?;
+        otel_layer = Some(
+            tracing_opentelemetry::layer().with_tracer(provider.tracer("shipyard-observability")),
+        );
+        providers.tracer = Some(provider);
+    }
+
+    registry
+        .with(fmt_layer)
+        .with(otel_layer)
+        .with(log_layer)
+        .try_init()?;
+
+    // Installed: only now publish the globals.
+    let _ = LOG_FILTER.set(filter_handle);
+    // W3C Trace Context propagation (traceparent/tracestate)
+    global::set_text_map_propagator(TraceContextPropagator::new());
+    if let Some(provider) = &providers.tracer {
+        global::set_tracer_provider(provider.clone());
+    }
+    if let Some(provider) = &providers.meter {
+        global::set_meter_provider(provider.clone());
     }
+
+    Ok(ObservabilityGuard::new(providers, cfg.shutdown_timeout))
+}
+
+fn init_traces(
+    endpoint: &str,
+    exporter: &ExporterConfig,
+    resource: Resource,
+    sampling: &SamplingConfig,
+) -> Result<TracerProvider, ObservabilityError> {
+    let exporter = exporter
+        .builder::<SpanExporterBuilder>(endpoint, Signal::Traces)?
+        .build_span_exporter()
+        .map_err(|e| e.to_string())
+        .map_err(ObservabilityError::exporter(Signal::Traces))?;
+    let batch = BatchSpanProcessor::builder(exporter, opentelemetry_sdk::runtime::Tokio).build();
+
+    Ok(TracerProvider::builder()
+        .with_config(
+            sdktrace::config()
+                .with_resource(resource)
+                .with_sampler(sampling.sampler()),
+        )
+        .with_span_processor(sampling::KeepErrors::new(batch))
+        .build())
+}
+
+/// Batched, bounded export: a full queue drops records instead of blocking the caller.
+fn init_logs(
+    endpoint: &str,
+    exporter: &ExporterConfig,
+    resource: Resource,
+) -> Result<LoggerProvider, ObservabilityError> {
+    let exporter = exporter.builder::<LogExporterBuilder>(endpoint, Signal::Logs)?;
+    opentelemetry_otlp::new_pipeline()
+        .logging()
+        .with_log_config(opentelemetry_sdk::logs::config().with_resource(resource))
+        // Defaults, overridable with OTEL_BLRP_* (queue size, batch size, delay, timeout)
+        .with_batch_config(BatchConfig::default())
+        .with_exporter(exporter)
+        .install_batch(opentelemetry_sdk::runtime::Tokio)
+        .map_err(|e| e.to_string())
+        .map_err(ObservabilityError::exporter(Signal::Logs))
+}
+
+fn init_metrics(
+    endpoint: &str,
+    exporter: &ExporterConfig,
+    resource: Resource,
+    cfg: MetricsConfig,
+) -> Result<SdkMeterProvider, ObservabilityError> {
+    let exporter = exporter
+        .builder::<MetricsExporterBuilder>(endpoint, Signal::Metrics)?
+        .build_metrics_exporter(
+            Box::new(DefaultTemporalitySelector::new()),
+            Box::new(DefaultAggregationSelector::new()),
+        )
+        .map_err(|e| e.to_string())
+        .map_err(ObservabilityError::exporter(Signal::Metrics))?;
+
+    let mut reader = PeriodicReader::builder(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_interval(cfg.export_interval);
+    if let Some(source) = cfg.prometheus {
+        reader = reader.with_producer(PrometheusBridge::new(source));
+    }
+
+    Ok(SdkMeterProvider::builder()
+        .with_resource(resource)
+        .with_reader(reader.build())
+        .build())
 }
 
-/// Shutdown hook to flush traces on exit (best-effort).
-pub fn shutdown() {
-    global::shutdown_tracer_provider();
+/// Replace the log filter installed by `init` (e.g. on config reload).
+///
+/// Invalid directives are rejected and the current filter stays in place.
+pub fn set_log_filter(directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|e| e.to_string())?;
+    let handle = LOG_FILTER
+        .get()
+        .ok_or_else(|| "observability not initialised".to_string())?;
+    handle.reload(filter).map_err(|e| e.to_string())?;
+    tracing::info!(filter = directives, "log.filter_changed");
+    Ok(())
 }
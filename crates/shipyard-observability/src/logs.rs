@@ -0,0 +1,154 @@
+//! OTLP log export: tracing events → OTel log records, correlated with traces.
+//!
+//! Every event that passes the log filter is also sent to the OTLP endpoint
+//! (stdout logs stay on). The record carries the trace/span id of the span it
+//! was emitted in as native log fields, not as attributes, so backends link logs
+//! and traces without parsing.
+//!
+//! Backpressure: records go through the SDK batch processor, whose queue is
+//! bounded (`OTEL_BLRP_MAX_QUEUE_SIZE`, default 2048). When the collector is down
+//! and the queue is full, new records are dropped; emitting never blocks the caller.
+
+use std::{borrow::Cow, time::SystemTime};
+
+use opentelemetry::{
+    Key,
+    logs::{AnyValue, LogRecord as _, Logger as _, LoggerProvider as _, Severity},
+    trace::{SamplingDecision, SpanContext, TraceContextExt, TraceFlags, TraceState},
+};
+use opentelemetry_sdk::logs::{Logger, LoggerProvider, TraceContext};
+use tracing::{Event, Level, Subscriber, field::Field};
+use tracing_opentelemetry::OtelData;
+use tracing_subscriber::{Layer, filter::filter_fn, layer::Context, registry::LookupSpan};
+
+/// Targets never exported as logs: the exporter's own stack would feed back into itself.
+const EXPORTER_TARGETS: &[&str] = &["opentelemetry", "tonic", "h2", "hyper", "tower", "reqwest"];
+
+/// Layer that forwards events to `provider`, skipping the exporter's own events.
+///
+/// Needs the tracing-opentelemetry layer in the same subscriber for trace correlation.
+pub fn layer<S>(provider: &LoggerProvider) -> impl Layer<S>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    OtlpLogLayer {
+        logger: provider.logger("shipyard-observability"),
+    }
+    .with_filter(filter_fn(|meta| {
+        !EXPORTER_TARGETS
+            .iter()
+            .any(|target| meta.target().starts_with(target))
+    }))
+}
+
+struct OtlpLogLayer {
+    logger: Logger,
+}
+
+impl<S> Layer<S> for OtlpLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let mut record = self.logger.create_log_record();
+        record.set_timestamp(SystemTime::now());
+        record.set_severity_number(severity(meta.level()));
+        record.set_severity_text(Cow::Borrowed(meta.level().as_str()));
+
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        if let Some(body) = fields.message {
+            record.set_body(body);
+        }
+        fields
+            .attributes
+            .push((Key::from_static_str("target"), meta.target().into()));
+        record.add_attributes(fields.attributes);
+
+        record.trace_context = ctx.event_span(event).and_then(|span| {
+            span.scope()
+                .find_map(|span| span.extensions().get::<OtelData>().and_then(trace_context))
+        });
+
+        self.logger.emit(record);
+    }
+}
+
+/// Ids of the span an event was emitted in, as assigned by the tracing-opentelemetry layer.
+pub(crate) fn trace_context(data: &OtelData) -> Option<TraceContext> {
+    let parent = data.parent_cx.span();
+    let parent = parent.span_context();
+    // A parent set after creation (`set_parent`) wins over the id picked at creation,
+    // as it does when the span is exported.
+    let trace_id = if parent.is_valid() {
+        parent.trace_id()
+    } else {
+        data.builder.trace_id?
+    };
+    let span_id = data.builder.span_id?;
+    let trace_flags = match &data.builder.sampling_result {
+        Some(result) if result.decision == SamplingDecision::RecordAndSample => TraceFlags::SAMPLED,
+        Some(_) => TraceFlags::default(),
+        None => parent.trace_flags(),
+    };
+    let span = SpanContext::new(trace_id, span_id, trace_flags, false, TraceState::default());
+    Some(TraceContext::from(&span))
+}
+
+fn severity(level: &Level) -> Severity {
+    match *level {
+        Level::TRACE => Severity::Trace,
+        Level::DEBUG => Severity::Debug,
+        Level::INFO => Severity::Info,
+        Level::WARN => Severity::Warn,
+        Level::ERROR => Severity::Error,
+    }
+}
+
+/// `message` becomes the body; every other field an attribute.
+#[derive(Default)]
+struct Fields {
+    message: Option<AnyValue>,
+    attributes: Vec<(Key, AnyValue)>,
+}
+
+impl Fields {
+    fn push(&mut self, field: &Field, value: AnyValue) {
+        if field.name() == "message" {
+            self.message = Some(value);
+        } else {
+            self.attributes
+                .push((Key::from_static_str(field.name()), value));
+        }
+    }
+}
+
+impl tracing::field::Visit for Fields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.push(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.push(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match i64::try_from(value) {
+            Ok(value) => self.push(field, value.into()),
+            Err(_) => self.push(field, value.to_string().into()),
+        }
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.push(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.push(field, value.to_string().into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.push(field, format!("{value:?}").into());
+    }
+}
@@ -0,0 +1,402 @@
+//! OTLP metric export, bridged from a Prometheus registry.
+//!
+//! Services keep recording into their `prometheus-client` registry (scraped on
+//! `/metrics`). When OTLP export is on, the same registry is read on every export
+//! cycle and converted to OTel data, so both exposure modes report the same series:
+//! - counter `http_requests` (`http_requests_total` when scraped) → monotonic cumulative sum
+//! - gauge → gauge
+//! - histogram → cumulative explicit-bucket histogram (same bounds)
+//!
+//! Instruments created through the OTel API (`opentelemetry::global::meter`) are
+//! exported on the same pipeline.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    fmt,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use opentelemetry::{
+    InstrumentationLibrary, KeyValue,
+    metrics::{MetricsError, Result as MetricsResult, Unit},
+};
+use opentelemetry_sdk::{
+    AttributeSet,
+    metrics::{
+        data::{
+            DataPoint, Gauge, Histogram, HistogramDataPoint, Metric, ScopeMetrics, Sum, Temporality,
+        },
+        reader::MetricProducer,
+    },
+};
+
+/// Instrumentation scope of bridged series.
+pub const BRIDGE_SCOPE: &str = "shipyard-observability/prometheus";
+
+/// Default OTLP metric export interval (the OTel default).
+pub const DEFAULT_EXPORT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct MetricsConfig {
+    /// How often metrics are pushed to `otlp_endpoint`
+    pub export_interval: Duration,
+
+    /// Prometheus registry to bridge; None exports OTel API instruments only
+    pub prometheus: Option<PrometheusSource>,
+}
+
+impl Default for MetricsConfig {
+    fn default() -> Self {
+        Self {
+            export_interval: DEFAULT_EXPORT_INTERVAL,
+            prometheus: None,
+        }
+    }
+}
+
+type EncodeFn = dyn Fn() -> String + Send + Sync;
+
+/// Renders a registry in Prometheus/OpenMetrics text format, e.g. `|| METRICS.encode()`.
+#[derive(Clone)]
+pub struct PrometheusSource(Arc<EncodeFn>);
+
+impl PrometheusSource {
+    pub fn new(encode: impl Fn() -> String + Send + Sync + 'static) -> Self {
+        Self(Arc::new(encode))
+    }
+}
+
+impl fmt::Debug for PrometheusSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PrometheusSource")
+    }
+}
+
+/// Converts a Prometheus registry to OTel metrics on each collection.
+#[derive(Debug)]
+pub struct PrometheusBridge {
+    source: PrometheusSource,
+    /// Reported as the start of every cumulative series
+    started: SystemTime,
+}
+
+impl PrometheusBridge {
+    pub fn new(source: PrometheusSource) -> Self {
+        Self {
+            source,
+            started: SystemTime::now(),
+        }
+    }
+}
+
+impl MetricProducer for PrometheusBridge {
+    fn produce(&self) -> MetricsResult<ScopeMetrics> {
+        let text = (self.source.0)();
+        let families = parse(&text).map_err(MetricsError::Other)?;
+        let now = SystemTime::now();
+        Ok(ScopeMetrics {
+            scope: InstrumentationLibrary::builder(BRIDGE_SCOPE).build(),
+            metrics: families
+                .into_iter()
+                .filter_map(|family| family.into_metric(self.started, now))
+                .collect(),
+        })
+    }
+}
+
+/// Sorted `(name, value)` pairs of one series.
+type Labels = Vec<(String, String)>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+    /// Types we do not bridge (info, stateset, summary, unknown)
+    Other,
+}
+
+#[derive(Debug)]
+struct Family {
+    name: String,
+    help: String,
+    unit: String,
+    kind: Kind,
+    /// Label set (without `le`) → samples
+    series: BTreeMap<Labels, Series>,
+}
+
+#[derive(Debug, Default)]
+struct Series {
+    value: Option<f64>,
+    sum: Option<f64>,
+    count: Option<f64>,
+    /// `(le, cumulative count)` in exposition order
+    buckets: Vec<(f64, f64)>,
+}
+
+impl Family {
+    fn into_metric(self, start: SystemTime, now: SystemTime) -> Option<Metric> {
+        let data: Box<dyn opentelemetry_sdk::metrics::data::Aggregation> = match self.kind {
+            Kind::Counter => Box::new(Sum {
+                data_points: points(&self.series, start, now),
+                temporality: Temporality::Cumulative,
+                is_monotonic: true,
+            }),
+            Kind::Gauge => Box::new(Gauge {
+                data_points: points(&self.series, start, now),
+            }),
+            Kind::Histogram => Box::new(Histogram {
+                data_points: self
+                    .series
+                    .iter()
+                    .map(|(labels, series)| histogram_point(labels, series, start, now))
+                    .collect(),
+                temporality: Temporality::Cumulative,
+            }),
+            Kind::Other => return None,
+        };
+        Some(Metric {
+            name: Cow::Owned(self.name),
+            description: Cow::Owned(self.help),
+            unit: Unit::new(self.unit),
+            data,
+        })
+    }
+}
+
+fn points(
+    series: &BTreeMap<Labels, Series>,
+    start: SystemTime,
+    now: SystemTime,
+) -> Vec<DataPoint<f64>> {
+    series
+        .iter()
+        .filter_map(|(labels, series)| {
+            Some(DataPoint {
+                attributes: attributes(labels),
+                start_time: Some(start),
+                time: Some(now),
+                value: series.value?,
+                exemplars: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+fn histogram_point(
+    labels: &[(String, String)],
+    series: &Series,
+    start: SystemTime,
+    now: SystemTime,
+) -> HistogramDataPoint<f64> {
+    // Prometheus buckets are cumulative and end with `+Inf`; OTel wants per-bucket counts.
+    let mut bounds = Vec::new();
+    let mut bucket_counts = Vec::new();
+    let mut previous = 0.0;
+    for &(le, cumulative) in &series.buckets {
+        if le.is_finite() {
+            bounds.push(le);
+        }
+        bucket_counts.push(to_count(cumulative - previous));
+        previous = cumulative;
+    }
+    if series.buckets.last().is_none_or(|(le, _)| le.is_finite()) {
+        // No `+Inf` bucket: the overflow bucket holds whatever `_count` has left.
+        bucket_counts.push(to_count(series.count.unwrap_or(previous) - previous));
+    }
+
+    HistogramDataPoint {
+        attributes: attributes(labels),
+        start_time: start,
+        time: now,
+        count: to_count(series.count.unwrap_or(previous)),
+        bounds,
+        bucket_counts,
+        min: None,
+        max: None,
+        sum: series.sum.unwrap_or(0.0),
+        exemplars: Vec::new(),
+    }
+}
+
+fn attributes(labels: &[(String, String)]) -> AttributeSet {
+    let kvs: Vec<KeyValue> = labels
+        .iter()
+        .map(|(k, v)| KeyValue::new(k.clone(), v.clone()))
+        .collect();
+    AttributeSet::from(kvs.as_slice())
+}
+
+fn to_count(value: f64) -> u64 {
+    value.max(0.0).round() as u64
+}
+
+/// Sample name suffixes within a family.
+const SUFFIXES: &[&str] = &[
+    "", "_total", "_bucket", "_sum", "_count", "_created", "_info",
+];
+
+/// Parse Prometheus text / OpenMetrics text exposition into families.
+fn parse(text: &str) -> Result<Vec<Family>, String> {
+    let mut families: Vec<Family> = Vec::new();
+
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line == "# EOF" {
+            continue;
+        }
+        if let Some(meta) = line.strip_prefix("# ") {
+            let mut parts = meta.splitn(3, ' ');
+            let (Some(keyword), Some(name)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let rest = parts.next().unwrap_or("").to_string();
+            let family = family_mut(&mut families, name);
+            match keyword {
+                "HELP" => family.help = rest,
+                "UNIT" => family.unit = rest,
+                "TYPE" => {
+                    family.kind = match rest.as_str() {
+                        "counter" => Kind::Counter,
+                        "gauge" => Kind::Gauge,
+                        "histogram" => Kind::Histogram,
+                        _ => Kind::Other,
+                    }
+                }
+                _ => {}
+            }
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+
+        let sample = parse_sample(line).map_err(|e| format!("line {}: {e}", n + 1))?;
+        let Some((family, suffix)) = families.iter_mut().find_map(|f| {
+            let suffix = sample.name.strip_prefix(f.name.as_str())?;
+            SUFFIXES.contains(&suffix).then_some((f, suffix))
+        }) else {
+            // Untyped sample without metadata: treat as a gauge of its own.
+            let family = family_mut(&mut families, &sample.name);
+            family.kind = Kind::Gauge;
+            family.series.entry(sample.labels).or_default().value = Some(sample.value);
+            continue;
+        };
+
+        let mut labels = sample.labels;
+        let le = labels
+            .iter()
+            .position(|(k, _)| k == "le")
+            .map(|i| labels.remove(i).1);
+        let series = family.series.entry(labels).or_default();
+        match (family.kind, suffix) {
+            (Kind::Histogram, "_bucket") => {
+                let le = le.ok_or("histogram bucket without `le`")?;
+                series.buckets.push((parse_value(&le)?, sample.value));
+            }
+            (Kind::Histogram, "_sum") => series.sum = Some(sample.value),
+            (Kind::Histogram, "_count") => series.count = Some(sample.value),
+            (Kind::Counter, "_total" | "") | (Kind::Gauge | Kind::Other, "") => {
+                series.value = Some(sample.value);
+            }
+            // `_created` and other auxiliary samples
+            _ => {}
+        }
+    }
+
+    Ok(families)
+}
+
+fn family_mut<'a>(families: &'a mut Vec<Family>, name: &str) -> &'a mut Family {
+    if let Some(i) = families.iter().position(|f| f.name == name) {
+        return &mut families[i];
+    }
+    families.push(Family {
+        name: name.to_string(),
+        help: String::new(),
+        unit: String::new(),
+        kind: Kind::Other,
+        series: BTreeMap::new(),
+    });
+    families.last_mut().expect("just pushed")
+}
+
+struct Sample {
+    name: String,
+    labels: Labels,
+    value: f64,
+}
+
+/// `name{k="v",...} value [timestamp]`
+fn parse_sample(line: &str) -> Result<Sample, String> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .ok_or("sample without value")?;
+    let name = line[..name_end].to_string();
+    let mut rest = &line[name_end..];
+
+    let mut labels = Vec::new();
+    if let Some(body) = rest.strip_prefix('{') {
+        let (parsed, after) = parse_labels(body)?;
+        labels = parsed;
+        rest = after;
+    }
+    let value = rest
+        .split_whitespace()
+        .next()
+        .ok_or("sample without value")?;
+
+    labels.sort();
+    Ok(Sample {
+        name,
+        labels,
+        value: parse_value(value)?,
+    })
+}
+
+/// Labels up to the closing `}`; returns them and the remainder after it.
+fn parse_labels(mut s: &str) -> Result<(Labels, &str), String> {
+    let mut labels = Vec::new();
+    loop {
+        s = s.trim_start_matches([',', ' ']);
+        if let Some(after) = s.strip_prefix('}') {
+            return Ok((labels, after));
+        }
+        let eq = s.find('=').ok_or("label without `=`")?;
+        let key = s[..eq].trim().to_string();
+        s = s[eq + 1..]
+            .strip_prefix('"')
+            .ok_or("label value not quoted")?;
+
+        let mut value = String::new();
+        let mut chars = s.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((i, '"')) => break i,
+                Some((_, '\\')) => match chars.next() {
+                    Some((_, 'n')) => value.push('\n'),
+                    Some((_, c)) => value.push(c),
+                    None => return Err("unterminated label value".into()),
+                },
+                Some((_, c)) => value.push(c),
+                None => return Err("unterminated label value".into()),
+            }
+        };
+        labels.push((key, value));
+        s = &s[end + 1..];
+    }
+}
+
+fn parse_value(s: &str) -> Result<f64, String> {
+    match s {
+        "+Inf" | "Inf" => Ok(f64::INFINITY),
+        "-Inf" => Ok(f64::NEG_INFINITY),
+        "NaN" => Ok(f64::NAN),
+        other => other
+            .parse()
+            .map_err(|_| format!("`{other}` is not a number")),
+    }
+}
@@ -0,0 +1,276 @@
+//! Log output on stdout: format and what each line includes.
+//!
+//! - `Json` (default): one object per event, for log shippers
+//! - `Pretty`: multi-line, coloured on a terminal, for reading locally
+//! - `Compact`: one coloured line per event
+//! - `Logfmt`: `key=value` pairs, greppable and still machine-readable
+//!
+//! `span_list` and `flatten_span_fields` apply to `Json` and `Logfmt`; the text formats
+//! always print the span scope inline.
+
+use std::{fmt, io::IsTerminal};
+
+use serde_json::{Map, Value};
+use tracing::{Event, Subscriber, field::Field};
+use tracing_subscriber::{
+    Layer,
+    field::RecordFields,
+    fmt::{
+        FmtContext, FormatEvent, FormatFields, FormattedFields, MakeWriter,
+        format::{JsonFields, Writer},
+        time::{FormatTime, SystemTime},
+    },
+    registry::{LookupSpan, Scope},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    #[default]
+    Json,
+    Pretty,
+    Compact,
+    Logfmt,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogOutput {
+    pub format: LogFormat,
+
+    /// Every span in scope (root first), not only the current one
+    pub span_list: bool,
+
+    /// The event's target (module path)
+    pub target: bool,
+
+    /// Source file and line of the event
+    pub file_line: bool,
+
+    /// Span fields (e.g. `request_id`, `path` of the request span) as top-level event fields
+    pub flatten_span_fields: bool,
+}
+
+impl Default for LogOutput {
+    /// Same lines as before formats were configurable: JSON with the current span and target.
+    fn default() -> Self {
+        Self {
+            format: LogFormat::Json,
+            span_list: false,
+            target: true,
+            file_line: false,
+            flatten_span_fields: false,
+        }
+    }
+}
+
+/// Formatting layer for `output`, writing to `writer` (stdout in `init`).
+pub fn layer<S, W>(output: &LogOutput, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let fmt = tracing_subscriber::fmt::layer().with_writer(writer);
+    match output.format {
+        LogFormat::Json if output.flatten_span_fields => fmt
+            .fmt_fields(JsonFields::new())
+            .event_format(FlatJson(output.clone()))
+            .boxed(),
+        LogFormat::Json => fmt
+            .json()
+            .with_current_span(true)
+            .with_span_list(output.span_list)
+            .with_target(output.target)
+            .with_file(output.file_line)
+            .with_line_number(output.file_line)
+            .boxed(),
+        LogFormat::Pretty => fmt
+            .pretty()
+            .with_ansi(std::io::stdout().is_terminal())
+            .with_target(output.target)
+            .with_file(output.file_line)
+            .with_line_number(output.file_line)
+            .boxed(),
+        LogFormat::Compact => fmt
+            .compact()
+            .with_ansi(std::io::stdout().is_terminal())
+            .with_target(output.target)
+            .with_file(output.file_line)
+            .with_line_number(output.file_line)
+            .boxed(),
+        LogFormat::Logfmt => fmt
+            .fmt_fields(LogfmtFields)
+            .event_format(Logfmt(output.clone()))
+            .boxed(),
+    }
+}
+
+fn timestamp() -> String {
+    let mut ts = String::new();
+    // Only fails on a broken clock; the key is then left empty.
+    let _ = SystemTime.format_time(&mut Writer::new(&mut ts));
+    ts
+}
+
+/// JSON with event and span fields at the top level; inner spans win over outer ones,
+/// event fields over span fields.
+struct FlatJson(LogOutput);
+
+impl<S> FormatEvent<S, JsonFields> for FlatJson
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, JsonFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        let mut line = Map::new();
+        line.insert("timestamp".into(), timestamp().into());
+        line.insert("level".into(), meta.level().as_str().into());
+
+        let mut spans = Vec::new();
+        for span in ctx.event_scope().into_iter().flat_map(Scope::from_root) {
+            let fields = span
+                .extensions()
+                .get::<FormattedFields<JsonFields>>()
+                .and_then(|f| serde_json::from_str::<Map<String, Value>>(&f.fields).ok())
+                .unwrap_or_default();
+            line.extend(fields.clone());
+            if self.0.span_list {
+                let mut entry = fields;
+                entry.insert("name".into(), span.name().into());
+                spans.push(Value::Object(entry));
+            }
+        }
+
+        let mut fields = String::new();
+        ctx.format_fields(Writer::new(&mut fields), event)?;
+        if let Ok(fields) = serde_json::from_str::<Map<String, Value>>(&fields) {
+            line.extend(fields);
+        }
+
+        if self.0.target {
+            line.insert("target".into(), meta.target().into());
+        }
+        if self.0.file_line {
+            line.insert("filename".into(), meta.file().into());
+            line.insert("line_number".into(), meta.line().into());
+        }
+        if self.0.span_list {
+            line.insert("spans".into(), spans.into());
+        }
+
+        let line = serde_json::to_string(&line).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// `ts=... level=info msg="..." key=value ...`
+struct Logfmt(LogOutput);
+
+impl<S> FormatEvent<S, LogfmtFields> for Logfmt
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, LogfmtFields>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let meta = event.metadata();
+        write!(
+            writer,
+            "ts={} level={}",
+            timestamp(),
+            meta.level().as_str().to_ascii_lowercase()
+        )?;
+        if self.0.target {
+            write!(writer, " target={}", quote(meta.target()))?;
+        }
+        if self.0.file_line
+            && let (Some(file), Some(line)) = (meta.file(), meta.line())
+        {
+            write!(writer, " location={}", quote(&format!("{file}:{line}")))?;
+        }
+
+        let mut fields = String::new();
+        ctx.format_fields(Writer::new(&mut fields), event)?;
+        if !fields.is_empty() {
+            write!(writer, " {fields}")?;
+        }
+
+        let scope: Vec<_> = ctx
+            .event_scope()
+            .into_iter()
+            .flat_map(Scope::from_root)
+            .collect();
+        if let Some(current) = scope.last() {
+            let names: Vec<&str> = scope.iter().map(|span| span.name()).collect();
+            if self.0.span_list {
+                write!(writer, " spans={}", quote(&names.join(">")))?;
+            } else {
+                write!(writer, " span={}", quote(current.name()))?;
+            }
+        }
+        if self.0.flatten_span_fields {
+            for span in &scope {
+                if let Some(fields) = span.extensions().get::<FormattedFields<LogfmtFields>>()
+                    && !fields.fields.is_empty()
+                {
+                    write!(writer, " {}", fields.fields)?;
+                }
+            }
+        }
+        writeln!(writer)
+    }
+}
+
+/// Fields as `key=value`, with `message` first as `msg`.
+struct LogfmtFields;
+
+impl<'writer> FormatFields<'writer> for LogfmtFields {
+    fn format_fields<R: RecordFields>(
+        &self,
+        mut writer: Writer<'writer>,
+        fields: R,
+    ) -> fmt::Result {
+        let mut visitor = LogfmtVisitor::default();
+        fields.record(&mut visitor);
+        let pairs: Vec<String> = visitor.message.into_iter().chain(visitor.pairs).collect();
+        writer.write_str(&pairs.join(" "))
+    }
+}
+
+#[derive(Default)]
+struct LogfmtVisitor {
+    message: Option<String>,
+    pairs: Vec<String>,
+}
+
+impl tracing::field::Visit for LogfmtVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "message" => self.message = Some(format!("msg={}", quote(value))),
+            name => self.pairs.push(format!("{name}={}", quote(value))),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{value:?}"));
+    }
+}
+
+/// Quote values that would otherwise break `key=value` parsing.
+fn quote(value: &str) -> String {
+    if !value.is_empty() && !value.contains([' ', '=', '"', '\\', '\n', '\t']) {
+        return value.to_string();
+    }
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
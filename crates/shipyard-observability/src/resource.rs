@@ -0,0 +1,80 @@
+//! The OTel resource shared by traces, metrics and logs.
+//!
+//! Layers, later wins:
+//! 1. detected: `service.instance.id` (random per process), `host.*`, `os.type`, `process.*`,
+//!    `telemetry.sdk.*`
+//! 2. from config: `service.version`, `deployment.environment`
+//! 3. `resource_attributes` (`OTEL_RESOURCE_ATTRIBUTES`)
+//! 4. `service.name`, which `OTEL_SERVICE_NAME` already decided (as in the OTel spec)
+
+use std::time::Duration;
+
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::{Resource, resource::TelemetryResourceDetector};
+
+use crate::ObservabilityConfig;
+
+/// Resource for everything `init` exports.
+pub fn resource(cfg: &ObservabilityConfig) -> Resource {
+    let detected = Resource::from_detectors(
+        Duration::from_secs(1),
+        vec![Box::new(TelemetryResourceDetector)],
+    )
+    .merge(&Resource::new(detect()));
+
+    let configured = Resource::new(
+        [
+            cfg.service_version
+                .clone()
+                .map(|v| KeyValue::new("service.version", v)),
+            cfg.deployment_environment
+                .clone()
+                .map(|e| KeyValue::new("deployment.environment", e)),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+
+    let from_env = Resource::new(
+        cfg.resource_attributes
+            .iter()
+            .map(|(k, v)| KeyValue::new(k.clone(), v.clone())),
+    );
+
+    detected
+        .merge(&configured)
+        .merge(&from_env)
+        .merge(&Resource::new([KeyValue::new(
+            "service.name",
+            cfg.service_name.clone(),
+        )]))
+}
+
+fn detect() -> Vec<KeyValue> {
+    let mut attributes = vec![
+        KeyValue::new("service.instance.id", uuid::Uuid::new_v4().to_string()),
+        KeyValue::new("host.arch", std::env::consts::ARCH),
+        KeyValue::new("os.type", std::env::consts::OS),
+        KeyValue::new("process.pid", i64::from(std::process::id())),
+        KeyValue::new("process.runtime.name", "rust"),
+    ];
+    if let Some(host) = host_name() {
+        attributes.push(KeyValue::new("host.name", host));
+    }
+    if let Some(exe) = std::env::current_exe()
+        .ok()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+    {
+        attributes.push(KeyValue::new("process.executable.name", exe));
+    }
+    attributes
+}
+
+/// `HOSTNAME` (set by shells and Kubernetes), else `/etc/hostname`.
+fn host_name() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
@@ -0,0 +1,205 @@
+//! Trace sampling: head sampling plus "always keep" rules.
+//!
+//! - `Sampler` is the standard head decision (`OTEL_TRACES_SAMPLER`): always on/off,
+//!   trace-id ratio, optionally parent-based.
+//! - `keep_routes`: requests whose `path` starts with one of these prefixes are always sampled.
+//! - `keep_errors`: spans that end with an error status are exported even when the head
+//!   decision dropped them. This needs the span to be recorded, so unsampled spans become
+//!   record-only (CPU cost, no export cost); the error span is kept, its unsampled
+//!   parents and children are not.
+
+use std::fmt;
+
+use opentelemetry::{
+    Context, KeyValue,
+    trace::{
+        Link, SamplingDecision, SamplingResult, SpanContext, SpanKind, Status, TraceContextExt,
+        TraceId, TraceResult, TraceState,
+    },
+};
+use opentelemetry_sdk::{
+    export::trace::SpanData,
+    trace::{Sampler as SdkSampler, ShouldSample, Span, SpanProcessor},
+};
+
+/// Span attributes matched against `keep_routes` (the `http.request` span records `path`).
+const ROUTE_ATTRIBUTES: &[&str] = &["path", "http.route", "url.path"];
+
+/// Head sampling decision for new traces.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Sampler {
+    AlwaysOn,
+    AlwaysOff,
+    /// Share of traces (0.0..=1.0), by trace id
+    TraceIdRatio(f64),
+    /// Follow the parent's decision; use the inner sampler for root spans
+    ParentBased(Box<Sampler>),
+}
+
+impl Default for Sampler {
+    /// `parentbased_always_on`, the OTel default.
+    fn default() -> Self {
+        Sampler::ParentBased(Box::new(Sampler::AlwaysOn))
+    }
+}
+
+impl Sampler {
+    /// From `OTEL_TRACES_SAMPLER` / `OTEL_TRACES_SAMPLER_ARG` values.
+    ///
+    /// The ratio defaults to 1.0 when `arg` is unset, as in the OTel spec.
+    pub fn from_otel(name: &str, arg: Option<f64>) -> Result<Self, String> {
+        let ratio = || match arg.unwrap_or(1.0) {
+            r if (0.0..=1.0).contains(&r) => Ok(r),
+            r => Err(format!("sampler ratio {r} is not between 0 and 1")),
+        };
+        let parent_based = |inner| Sampler::ParentBased(Box::new(inner));
+        Ok(match name {
+            "always_on" => Sampler::AlwaysOn,
+            "always_off" => Sampler::AlwaysOff,
+            "traceidratio" => Sampler::TraceIdRatio(ratio()?),
+            "parentbased_always_on" => parent_based(Sampler::AlwaysOn),
+            "parentbased_always_off" => parent_based(Sampler::AlwaysOff),
+            "parentbased_traceidratio" => parent_based(Sampler::TraceIdRatio(ratio()?)),
+            other => return Err(format!("unsupported sampler `{other}`")),
+        })
+    }
+
+    fn to_sdk(&self) -> SdkSampler {
+        match self {
+            Sampler::AlwaysOn => SdkSampler::AlwaysOn,
+            Sampler::AlwaysOff => SdkSampler::AlwaysOff,
+            Sampler::TraceIdRatio(ratio) => SdkSampler::TraceIdRatioBased(*ratio),
+            Sampler::ParentBased(inner) => SdkSampler::ParentBased(Box::new(inner.to_sdk())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SamplingConfig {
+    pub sampler: Sampler,
+
+    /// Export spans that end with an error status even if the head decision dropped them
+    pub keep_errors: bool,
+
+    /// Always sample requests whose path starts with one of these (e.g. `/api/v1/orders`)
+    pub keep_routes: Vec<String>,
+}
+
+impl SamplingConfig {
+    /// Sampler to install in the tracer provider.
+    pub fn sampler(&self) -> RuleSampler {
+        RuleSampler {
+            inner: self.sampler.to_sdk(),
+            keep_errors: self.keep_errors,
+            keep_routes: self.keep_routes.clone(),
+        }
+    }
+}
+
+/// `Sampler` plus the keep rules; pairs with `KeepErrors` when `keep_errors` is set.
+#[derive(Clone)]
+pub struct RuleSampler {
+    inner: SdkSampler,
+    keep_errors: bool,
+    keep_routes: Vec<String>,
+}
+
+impl fmt::Debug for RuleSampler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RuleSampler")
+            .field("keep_errors", &self.keep_errors)
+            .field("keep_routes", &self.keep_routes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ShouldSample for RuleSampler {
+    fn should_sample(
+        &self,
+        parent_context: Option<&Context>,
+        trace_id: TraceId,
+        name: &str,
+        span_kind: &SpanKind,
+        attributes: &[KeyValue],
+        links: &[Link],
+    ) -> SamplingResult {
+        if self.keeps_route(attributes) {
+            return SamplingResult {
+                decision: SamplingDecision::RecordAndSample,
+                attributes: Vec::new(),
+                trace_state: trace_state(parent_context),
+            };
+        }
+
+        let mut result =
+            self.inner
+                .should_sample(parent_context, trace_id, name, span_kind, attributes, links);
+        if self.keep_errors && result.decision == SamplingDecision::Drop {
+            // Recorded so `KeepErrors` can see the final status.
+            result.decision = SamplingDecision::RecordOnly;
+        }
+        result
+    }
+}
+
+impl RuleSampler {
+    fn keeps_route(&self, attributes: &[KeyValue]) -> bool {
+        !self.keep_routes.is_empty()
+            && attributes.iter().any(|kv| {
+                ROUTE_ATTRIBUTES.contains(&kv.key.as_str()) && {
+                    let value = kv.value.as_str();
+                    self.keep_routes
+                        .iter()
+                        .any(|route| value.starts_with(route.as_str()))
+                }
+            })
+    }
+}
+
+fn trace_state(parent_context: Option<&Context>) -> TraceState {
+    parent_context
+        .filter(|cx| cx.has_active_span())
+        .map(|cx| cx.span().span_context().trace_state().clone())
+        .unwrap_or_default()
+}
+
+/// Marks unsampled spans that ended with an error as sampled, then hands every
+/// span to `inner` (which drops the remaining unsampled ones).
+#[derive(Debug)]
+pub struct KeepErrors<P> {
+    inner: P,
+}
+
+impl<P> KeepErrors<P> {
+    pub fn new(inner: P) -> Self {
+        Self { inner }
+    }
+}
+
+impl<P: SpanProcessor> SpanProcessor for KeepErrors<P> {
+    fn on_start(&self, span: &mut Span, cx: &Context) {
+        self.inner.on_start(span, cx);
+    }
+
+    fn on_end(&self, mut span: SpanData) {
+        let sc = &span.span_context;
+        if !sc.is_sampled() && matches!(span.status, Status::Error { .. }) {
+            span.span_context = SpanContext::new(
+                sc.trace_id(),
+                sc.span_id(),
+                sc.trace_flags().with_sampled(true),
+                sc.is_remote(),
+                sc.trace_state().clone(),
+            );
+        }
+        self.inner.on_end(span);
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        self.inner.force_flush()
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        self.inner.shutdown()
+    }
+}
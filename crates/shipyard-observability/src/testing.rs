@@ -0,0 +1,465 @@
+//! In-memory span and log capture for tests (`testing` feature).
+//!
+//! `capture()` installs a subscriber for the current thread until the returned
+//! `Telemetry` is dropped, so tests running in parallel never see each other's
+//! telemetry. Spans also get OTel ids (always sampled), as with `init`, so `trace_id`
+//! and `span_id` fields behave as in production.
+//!
+//! Only the current thread is captured: use `#[tokio::test]` (current-thread runtime)
+//! and `oneshot` the router rather than serving it from spawned tasks.
+//!
+//! ```ignore
+//! let telemetry = shipyard_observability::testing::capture();
+//! let res = app.oneshot(request).await?;
+//!
+//! let span = telemetry.spans().named("orders.create").child_of("http.request").one();
+//! assert_eq!(span.field("request_id"), Some(request_id));
+//! telemetry.logs().message("request.completed").with_field("status", 201).one();
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::trace::TracerProvider;
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+    span,
+    subscriber::DefaultGuard,
+};
+use tracing_opentelemetry::OtelData;
+use tracing_subscriber::{Layer, layer::Context, layer::SubscriberExt, registry::LookupSpan};
+
+use crate::logs::trace_context;
+
+/// Start capturing spans and log events on this thread.
+pub fn capture() -> Telemetry {
+    let store = Store::default();
+    let provider = TracerProvider::builder().build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("shipyard-observability")))
+        .with(CaptureLayer(store.clone()));
+
+    Telemetry {
+        _guard: tracing::subscriber::set_default(subscriber),
+        _provider: provider,
+        store,
+    }
+}
+
+/// What was captured since `capture()`; capture stops when dropped.
+pub struct Telemetry {
+    // Dropped first: restores the previous subscriber.
+    _guard: DefaultGuard,
+    _provider: TracerProvider,
+    store: Store,
+}
+
+impl Telemetry {
+    /// Query over the spans created so far (open or closed), in creation order.
+    pub fn spans(&self) -> SpanQuery {
+        SpanQuery(Query::new(self.store.snapshot()))
+    }
+
+    /// Query over the log events emitted so far, in order.
+    pub fn logs(&self) -> LogQuery {
+        LogQuery(Query::new(self.store.snapshot()))
+    }
+
+    /// Direct parent of `span`, if any.
+    pub fn parent(&self, span: &CapturedSpan) -> Option<CapturedSpan> {
+        let snapshot = self.store.snapshot();
+        span.parent.map(|index| snapshot.spans[index].clone())
+    }
+
+    /// Innermost span `log` was emitted in, if any.
+    pub fn span_of(&self, log: &CapturedLog) -> Option<CapturedSpan> {
+        let snapshot = self.store.snapshot();
+        log.span.map(|index| snapshot.spans[index].clone())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapturedSpan {
+    pub name: &'static str,
+    pub target: String,
+
+    /// Fields recorded at creation or later (`Span::record`); empty ones are absent.
+    pub fields: BTreeMap<String, String>,
+
+    /// Hex ids assigned by the OTel layer.
+    pub trace_id: Option<String>,
+    pub span_id: Option<String>,
+
+    parent: Option<usize>,
+}
+
+impl CapturedSpan {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CapturedLog {
+    pub level: Level,
+    pub target: String,
+
+    /// The `message` field (empty when the event has none).
+    pub message: String,
+
+    /// Every field except `message`.
+    pub fields: BTreeMap<String, String>,
+
+    span: Option<usize>,
+}
+
+impl CapturedLog {
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// Filters over captured spans; every filter must match.
+pub struct SpanQuery(Query<CapturedSpan>);
+
+impl SpanQuery {
+    pub fn named(self, name: &str) -> Self {
+        let name = name.to_string();
+        Self(
+            self.0
+                .filter(format!("named `{name}`"), move |_, span| span.name == name),
+        )
+    }
+
+    /// Field `name` recorded with `value` (compared as displayed, e.g. `201` or `GET`).
+    pub fn with_field(self, name: &str, value: impl fmt::Display) -> Self {
+        let (name, value) = (name.to_string(), value.to_string());
+        Self(
+            self.0
+                .filter(format!("with {name}={value}"), move |_, span| {
+                    span.field(&name) == Some(value.as_str())
+                }),
+        )
+    }
+
+    /// Field `name` recorded with any value.
+    pub fn has_field(self, name: &str) -> Self {
+        let name = name.to_string();
+        Self(
+            self.0
+                .filter(format!("with field `{name}`"), move |_, span| {
+                    span.fields.contains_key(&name)
+                }),
+        )
+    }
+
+    /// Direct parent is named `parent`.
+    pub fn child_of(self, parent: &str) -> Self {
+        let parent = parent.to_string();
+        Self(
+            self.0
+                .filter(format!("child of `{parent}`"), move |snapshot, span| {
+                    span.parent
+                        .is_some_and(|index| snapshot.spans[index].name == parent)
+                }),
+        )
+    }
+
+    /// Some ancestor (parent, grandparent, ...) is named `ancestor`.
+    pub fn within(self, ancestor: &str) -> Self {
+        let ancestor = ancestor.to_string();
+        Self(
+            self.0
+                .filter(format!("within `{ancestor}`"), move |snapshot, span| {
+                    snapshot
+                        .scope(span.parent)
+                        .any(|span| span.name == ancestor)
+                }),
+        )
+    }
+
+    pub fn all(self) -> Vec<CapturedSpan> {
+        self.0.all()
+    }
+
+    pub fn count(self) -> usize {
+        self.0.all().len()
+    }
+
+    pub fn exists(self) -> bool {
+        self.count() > 0
+    }
+
+    /// The only matching span; panics listing what was captured otherwise.
+    pub fn one(self) -> CapturedSpan {
+        self.0
+            .one("span", |span| format!("{} {:?}", span.name, span.fields))
+    }
+}
+
+/// Filters over captured log events; every filter must match.
+pub struct LogQuery(Query<CapturedLog>);
+
+impl LogQuery {
+    pub fn message(self, message: &str) -> Self {
+        let message = message.to_string();
+        Self(
+            self.0
+                .filter(format!("message `{message}`"), move |_, log| {
+                    log.message == message
+                }),
+        )
+    }
+
+    pub fn level(self, level: Level) -> Self {
+        Self(
+            self.0
+                .filter(format!("at {level}"), move |_, log| log.level == level),
+        )
+    }
+
+    /// Field `name` recorded with `value` (compared as displayed, e.g. `201` or `GET`).
+    pub fn with_field(self, name: &str, value: impl fmt::Display) -> Self {
+        let (name, value) = (name.to_string(), value.to_string());
+        Self(
+            self.0
+                .filter(format!("with {name}={value}"), move |_, log| {
+                    log.field(&name) == Some(value.as_str())
+                }),
+        )
+    }
+
+    /// Field `name` recorded with any value.
+    pub fn has_field(self, name: &str) -> Self {
+        let name = name.to_string();
+        Self(
+            self.0
+                .filter(format!("with field `{name}`"), move |_, log| {
+                    log.fields.contains_key(&name)
+                }),
+        )
+    }
+
+    /// Emitted inside a span named `span` (the innermost one or any of its ancestors).
+    pub fn in_span(self, span: &str) -> Self {
+        let span = span.to_string();
+        Self(
+            self.0
+                .filter(format!("in span `{span}`"), move |snapshot, log| {
+                    snapshot.scope(log.span).any(|s| s.name == span)
+                }),
+        )
+    }
+
+    pub fn all(self) -> Vec<CapturedLog> {
+        self.0.all()
+    }
+
+    pub fn count(self) -> usize {
+        self.0.all().len()
+    }
+
+    pub fn exists(self) -> bool {
+        self.count() > 0
+    }
+
+    /// The only matching event; panics listing what was captured otherwise.
+    pub fn one(self) -> CapturedLog {
+        self.0
+            .one("log", |log| format!("{} {:?}", log.message, log.fields))
+    }
+}
+
+type Filter<T> = Box<dyn Fn(&Snapshot, &T) -> bool>;
+
+struct Query<T> {
+    snapshot: Snapshot,
+    filters: Vec<Filter<T>>,
+    description: Vec<String>,
+}
+
+impl<T: Clone + Items> Query<T> {
+    fn new(snapshot: Snapshot) -> Self {
+        Self {
+            snapshot,
+            filters: Vec::new(),
+            description: Vec::new(),
+        }
+    }
+
+    fn filter(
+        mut self,
+        description: String,
+        filter: impl Fn(&Snapshot, &T) -> bool + 'static,
+    ) -> Self {
+        self.filters.push(Box::new(filter));
+        self.description.push(description);
+        self
+    }
+
+    fn all(&self) -> Vec<T> {
+        T::items(&self.snapshot)
+            .iter()
+            .filter(|item| self.filters.iter().all(|f| f(&self.snapshot, item)))
+            .cloned()
+            .collect()
+    }
+
+    fn one(self, kind: &str, show: impl Fn(&T) -> String) -> T {
+        let mut matches = self.all();
+        if matches.len() == 1 {
+            return matches.remove(0);
+        }
+        let captured: Vec<String> = T::items(&self.snapshot).iter().map(show).collect();
+        panic!(
+            "expected one {kind} {}, found {}; captured:\n  {}",
+            self.description.join(", "),
+            matches.len(),
+            captured.join("\n  ")
+        );
+    }
+}
+
+trait Items: Sized {
+    fn items(snapshot: &Snapshot) -> &[Self];
+}
+
+impl Items for CapturedSpan {
+    fn items(snapshot: &Snapshot) -> &[Self] {
+        &snapshot.spans
+    }
+}
+
+impl Items for CapturedLog {
+    fn items(snapshot: &Snapshot) -> &[Self] {
+        &snapshot.logs
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct Snapshot {
+    spans: Vec<CapturedSpan>,
+    logs: Vec<CapturedLog>,
+}
+
+impl Snapshot {
+    /// `span` and its ancestors, innermost first.
+    fn scope(&self, span: Option<usize>) -> impl Iterator<Item = &CapturedSpan> {
+        std::iter::successors(span.map(|index| &self.spans[index]), |span| {
+            span.parent.map(|index| &self.spans[index])
+        })
+    }
+}
+
+#[derive(Clone, Default)]
+struct Store(Arc<Mutex<Snapshot>>);
+
+impl Store {
+    fn snapshot(&self) -> Snapshot {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+/// Position of a span in `Snapshot::spans` (tracing recycles span ids).
+struct SpanIndex(usize);
+
+struct CaptureLayer(Store);
+
+impl<S> Layer<S> for CaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let parent = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<SpanIndex>().map(|i| i.0));
+        let ids = span.extensions().get::<OtelData>().and_then(trace_context);
+
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+
+        let mut snapshot = self.0.0.lock().unwrap();
+        let index = snapshot.spans.len();
+        snapshot.spans.push(CapturedSpan {
+            name: span.name(),
+            target: span.metadata().target().to_string(),
+            fields: fields.all,
+            trace_id: ids.as_ref().map(|ids| ids.trace_id.to_string()),
+            span_id: ids.as_ref().map(|ids| ids.span_id.to_string()),
+            parent,
+        });
+        drop(snapshot);
+        span.extensions_mut().insert(SpanIndex(index));
+    }
+
+    // Ids can change after creation when a remote parent is attached with `set_parent`.
+    fn on_enter(&self, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let Some(index) = span.extensions().get::<SpanIndex>().map(|i| i.0) else {
+            return;
+        };
+        let ids = span.extensions().get::<OtelData>().and_then(trace_context);
+
+        let captured = &mut self.0.0.lock().unwrap().spans[index];
+        captured.trace_id = ids.as_ref().map(|ids| ids.trace_id.to_string());
+        captured.span_id = ids.as_ref().map(|ids| ids.span_id.to_string());
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        let Some(index) = ctx
+            .span(id)
+            .and_then(|span| span.extensions().get::<SpanIndex>().map(|i| i.0))
+        else {
+            return;
+        };
+        let mut fields = Fields::default();
+        values.record(&mut fields);
+        self.0.0.lock().unwrap().spans[index]
+            .fields
+            .extend(fields.all);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let span = ctx
+            .event_span(event)
+            .and_then(|span| span.extensions().get::<SpanIndex>().map(|i| i.0));
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let message = fields.all.remove("message").unwrap_or_default();
+
+        self.0.0.lock().unwrap().logs.push(CapturedLog {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_string(),
+            message,
+            fields: fields.all,
+            span,
+        });
+    }
+}
+
+/// Every field as displayed (`%value` and `?value` fields as their `Display`/`Debug` text).
+#[derive(Default)]
+struct Fields {
+    all: BTreeMap<String, String>,
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.all.insert(field.name().to_string(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.all
+            .insert(field.name().to_string(), format!("{value:?}"));
+    }
+}
@@ -0,0 +1,71 @@
+//! `init` installs process-wide state: every case runs in this one test, in order.
+
+use std::{
+    net::TcpListener,
+    time::{Duration, Instant},
+};
+
+use shipyard_observability::{
+    DEFAULT_SHUTDOWN_TIMEOUT, ObservabilityConfig, ObservabilityError, SamplingConfig,
+};
+
+fn config(otlp_endpoint: Option<String>) -> ObservabilityConfig {
+    ObservabilityConfig {
+        service_name: "guard-test".into(),
+        service_version: None,
+        deployment_environment: None,
+        resource_attributes: Vec::new(),
+        otlp_endpoint,
+        exporter: Default::default(),
+        log_output: Default::default(),
+        log_filter: Some("info".into()),
+        metrics: None,
+        sampling: SamplingConfig::default(),
+        export_logs: true,
+        shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+    }
+}
+
+/// Accepts connections and never answers.
+fn black_hole() -> (TcpListener, String) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    (listener, endpoint)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn init_is_fallible_and_the_guard_bounds_shutdown() {
+    // Rejected before anything global is touched
+    let err = shipyard_observability::init(ObservabilityConfig {
+        log_filter: Some("info,=debug[".into()),
+        ..config(None)
+    })
+    .unwrap_err();
+    assert!(matches!(err, ObservabilityError::LogFilter { .. }), "{err}");
+
+    let (_collector, endpoint) = black_hole();
+    let guard = shipyard_observability::init(ObservabilityConfig {
+        shutdown_timeout: Duration::from_millis(500),
+        ..config(Some(endpoint.clone()))
+    })
+    .unwrap();
+
+    // Only one subscriber per process
+    let err = shipyard_observability::init(config(Some(endpoint))).unwrap_err();
+    assert!(
+        matches!(err, ObservabilityError::AlreadyInitialised(_)),
+        "{err}"
+    );
+
+    tracing::info_span!("pending").in_scope(|| tracing::info!("pending record"));
+
+    // The collector never answers: dropping the guard gives up after the timeout. Dropped
+    // on a runtime worker, which must not stall the runtime.
+    let started = Instant::now();
+    drop(guard);
+    assert!(
+        started.elapsed() < Duration::from_secs(3),
+        "{:?}",
+        started.elapsed()
+    );
+}
@@ -0,0 +1,137 @@
+use std::{
+    io,
+    sync::{Arc, Mutex},
+};
+
+use serde_json::Value;
+use shipyard_observability::{LogFormat, LogOutput, output};
+use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+/// Collects everything the layer writes.
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl io::Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'a> MakeWriter<'a> for Buffer {
+    type Writer = Buffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+/// Logs one event inside a request span and a child span; returns the line written.
+fn log_line(output: &LogOutput) -> String {
+    let buffer = Buffer::default();
+    let subscriber = tracing_subscriber::registry().with(output::layer(output, buffer.clone()));
+    tracing::subscriber::with_default(subscriber, || {
+        let request = tracing::info_span!(
+            "http.request",
+            request_id = "req-1",
+            path = "/api/v1/orders",
+            tenant_id = tracing::field::Empty,
+        );
+        request.record("tenant_id", "acme");
+        let _request = request.enter();
+        let _db = tracing::info_span!("db.query", path = "orders").entered();
+        tracing::info!(status = 200, "request completed");
+    });
+    let bytes = buffer.0.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+fn json(line: &str) -> Value {
+    serde_json::from_str(line.trim()).unwrap()
+}
+
+#[test]
+fn default_is_json_with_the_current_span_nested() {
+    let line = json(&log_line(&LogOutput::default()));
+
+    assert_eq!(line["level"], "INFO");
+    assert_eq!(line["fields"]["message"], "request completed");
+    assert_eq!(line["span"]["name"], "db.query");
+    assert_eq!(line["target"], "log_output");
+    assert!(line.get("request_id").is_none());
+    assert!(line.get("spans").is_none() && line.get("filename").is_none());
+}
+
+#[test]
+fn flattened_json_puts_span_fields_at_the_top_level() {
+    let line = json(&log_line(&LogOutput {
+        span_list: true,
+        file_line: true,
+        target: false,
+        flatten_span_fields: true,
+        ..LogOutput::default()
+    }));
+
+    assert_eq!(line["message"], "request completed");
+    assert_eq!(line["status"], 200);
+    assert_eq!(line["request_id"], "req-1");
+    assert_eq!(line["tenant_id"], "acme", "fields recorded after creation");
+    assert_eq!(line["path"], "orders", "inner span wins");
+    assert_eq!(line["spans"][0]["name"], "http.request");
+    assert_eq!(line["spans"][1]["name"], "db.query");
+    assert!(
+        line["filename"]
+            .as_str()
+            .unwrap()
+            .ends_with("log_output.rs")
+    );
+    assert!(line.get("target").is_none());
+}
+
+#[test]
+fn logfmt_quotes_values_and_optionally_inlines_span_fields() {
+    let plain = log_line(&LogOutput {
+        format: LogFormat::Logfmt,
+        ..LogOutput::default()
+    });
+    assert!(plain.starts_with("ts="), "{plain}");
+    assert!(
+        plain.contains(
+            r#" level=info target=log_output msg="request completed" status=200 span=db.query"#
+        ),
+        "{plain}"
+    );
+    assert!(!plain.contains("request_id"), "{plain}");
+
+    let flat = log_line(&LogOutput {
+        format: LogFormat::Logfmt,
+        span_list: true,
+        flatten_span_fields: true,
+        ..LogOutput::default()
+    });
+    assert!(
+        flat.trim_end().ends_with(
+            "spans=http.request>db.query request_id=req-1 path=/api/v1/orders tenant_id=acme path=orders"
+        ),
+        "{flat}"
+    );
+}
+
+#[test]
+fn text_formats_honour_target_and_file_line() {
+    for format in [LogFormat::Pretty, LogFormat::Compact] {
+        let line = log_line(&LogOutput {
+            format,
+            target: false,
+            file_line: true,
+            ..LogOutput::default()
+        });
+        assert!(line.contains("request completed"), "{line}");
+        assert!(line.contains("log_output.rs"), "{format:?}: {line}");
+        assert!(!line.contains("log_output:"), "{format:?}: {line}");
+    }
+}
@@ -0,0 +1,109 @@
+use std::sync::{Arc, Mutex};
+
+use opentelemetry::{
+    logs::{AnyValue, LogResult, Severity},
+    trace::{TraceContextExt, TracerProvider as _},
+};
+use opentelemetry_sdk::{
+    export::logs::LogData,
+    logs::{LogProcessor, LoggerProvider},
+    trace::TracerProvider,
+};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Keeps every emitted record in memory.
+#[derive(Debug, Clone, Default)]
+struct Capture(Arc<Mutex<Vec<LogData>>>);
+
+impl LogProcessor for Capture {
+    fn emit(&self, data: LogData) {
+        self.0.lock().unwrap().push(data);
+    }
+
+    fn force_flush(&self) -> LogResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> LogResult<()> {
+        Ok(())
+    }
+}
+
+fn attribute<'a>(data: &'a LogData, key: &str) -> Option<&'a AnyValue> {
+    data.record
+        .attributes
+        .as_ref()?
+        .iter()
+        .find(|(k, _)| k.as_str() == key)
+        .map(|(_, v)| v)
+}
+
+#[test]
+fn events_carry_the_trace_and_span_of_their_span() {
+    let capture = Capture::default();
+    let logs = LoggerProvider::builder()
+        .with_log_processor(capture.clone())
+        .build();
+    let tracer = TracerProvider::builder().build().tracer("test");
+
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(shipyard_observability::logs::layer(&logs));
+
+    let (trace_id, span_id) = tracing::subscriber::with_default(subscriber, || {
+        let outer = tracing::info_span!("http.request");
+        let _outer = outer.enter();
+        let inner = tracing::info_span!("orders.create");
+        let _inner = inner.enter();
+
+        tracing::warn!(order_id = 42, tenant = "acme", "order.rejected");
+        tracing::info!(target: "h2::codec", "exporter internals are not exported");
+
+        let cx = inner.context();
+        let span = cx.span();
+        (
+            span.span_context().trace_id(),
+            span.span_context().span_id(),
+        )
+    });
+
+    let records = capture.0.lock().unwrap();
+    assert_eq!(records.len(), 1, "exporter targets are filtered out");
+    let data = &records[0];
+
+    let trace = data.record.trace_context.as_ref().expect("trace context");
+    assert_eq!(trace.trace_id, trace_id);
+    assert_eq!(trace.span_id, span_id, "innermost span");
+
+    assert_eq!(data.record.severity_number, Some(Severity::Warn));
+    assert_eq!(
+        data.record.body,
+        Some(AnyValue::String("order.rejected".into()))
+    );
+    assert_eq!(attribute(data, "order_id"), Some(&AnyValue::Int(42)));
+    assert_eq!(
+        attribute(data, "tenant"),
+        Some(&AnyValue::String("acme".into()))
+    );
+}
+
+#[test]
+fn events_outside_spans_have_no_trace_context() {
+    let capture = Capture::default();
+    let logs = LoggerProvider::builder()
+        .with_log_processor(capture.clone())
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(
+            tracing_opentelemetry::layer()
+                .with_tracer(TracerProvider::builder().build().tracer("test")),
+        )
+        .with(shipyard_observability::logs::layer(&logs));
+
+    tracing::subscriber::with_default(subscriber, || tracing::info!("service.started"));
+
+    let records = capture.0.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    assert!(records[0].record.trace_context.is_none());
+}
@@ -0,0 +1,165 @@
+use opentelemetry::{Key, KeyValue, Value};
+use opentelemetry_sdk::metrics::{
+    data::{Gauge, Histogram, Metric, Sum, Temporality},
+    reader::MetricProducer,
+};
+use prometheus_client::{
+    encoding::{EncodeLabelSet, text::encode},
+    metrics::{
+        counter::Counter, family::Family, gauge::Gauge as PromGauge,
+        histogram::Histogram as PromHistogram,
+    },
+    registry::Registry,
+};
+use shipyard_observability::{PrometheusBridge, PrometheusSource, metrics::BRIDGE_SCOPE};
+use std::sync::Arc;
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RouteLabels {
+    route: String,
+    status: String,
+}
+
+fn labels(route: &str, status: &str) -> RouteLabels {
+    RouteLabels {
+        route: route.to_string(),
+        status: status.to_string(),
+    }
+}
+
+fn bridge_text(text: &'static str) -> PrometheusBridge {
+    PrometheusBridge::new(PrometheusSource::new(move || text.to_string()))
+}
+
+fn find<'a>(metrics: &'a [Metric], name: &str) -> &'a Metric {
+    metrics
+        .iter()
+        .find(|m| m.name == name)
+        .unwrap_or_else(|| panic!("metric {name} not bridged"))
+}
+
+fn attr(attrs: impl Iterator<Item = (Key, Value)>, key: &str) -> Option<String> {
+    let kvs: Vec<KeyValue> = attrs.map(|(k, v)| KeyValue::new(k, v)).collect();
+    kvs.into_iter()
+        .find(|kv| kv.key.as_str() == key)
+        .map(|kv| kv.value.to_string())
+}
+
+#[test]
+fn registry_series_are_bridged_with_the_same_names_labels_and_values() {
+    let requests: Family<RouteLabels, Counter<u64>> = Family::default();
+    let latency: Family<RouteLabels, PromHistogram> =
+        Family::new_with_constructor(|| PromHistogram::new([0.1, 0.5].into_iter()));
+    let in_flight: PromGauge = PromGauge::default();
+
+    let mut registry = Registry::default();
+    registry.register("http_requests", "Total HTTP requests.", requests.clone());
+    registry.register("http_request_duration_seconds", "Latency.", latency.clone());
+    registry.register("in_flight", "Requests in flight.", in_flight.clone());
+
+    requests.get_or_create(&labels("/orders", "200")).inc_by(3);
+    requests.get_or_create(&labels("/orders", "500")).inc();
+    for secs in [0.05, 0.2, 0.3, 2.0] {
+        latency
+            .get_or_create(&labels("/orders", "200"))
+            .observe(secs);
+    }
+    in_flight.set(7);
+
+    let registry = Arc::new(registry);
+    let bridge = PrometheusBridge::new(PrometheusSource::new(move || {
+        let mut out = String::new();
+        encode(&mut out, &registry).expect("encode");
+        out
+    }));
+    let scope = bridge.produce().expect("produce");
+    assert_eq!(scope.scope.name, BRIDGE_SCOPE);
+
+    let counter = find(&scope.metrics, "http_requests");
+    assert!(counter.description.starts_with("Total HTTP requests"));
+    let sum = counter
+        .data
+        .as_any()
+        .downcast_ref::<Sum<f64>>()
+        .expect("counter bridged as a sum");
+    assert!(sum.is_monotonic);
+    assert_eq!(sum.temporality, Temporality::Cumulative);
+    let mut by_status: Vec<(String, f64)> = sum
+        .data_points
+        .iter()
+        .map(|dp| {
+            let attrs = dp.attributes.iter().map(|(k, v)| (k.clone(), v.clone()));
+            (attr(attrs, "status").expect("status label"), dp.value)
+        })
+        .collect();
+    by_status.sort_by(|a, b| a.0.cmp(&b.0));
+    assert_eq!(
+        by_status,
+        vec![("200".to_string(), 3.0), ("500".to_string(), 1.0)]
+    );
+
+    let histogram = find(&scope.metrics, "http_request_duration_seconds")
+        .data
+        .as_any()
+        .downcast_ref::<Histogram<f64>>()
+        .expect("histogram bridged as a histogram");
+    let point = &histogram.data_points[0];
+    assert_eq!(point.bounds, vec![0.1, 0.5]);
+    assert_eq!(
+        point.bucket_counts,
+        vec![1, 2, 1],
+        "per-bucket, not cumulative"
+    );
+    assert_eq!(point.count, 4);
+    assert!((point.sum - 2.55).abs() < 1e-9);
+    let attrs = point.attributes.iter().map(|(k, v)| (k.clone(), v.clone()));
+    assert_eq!(attr(attrs, "le"), None, "`le` is folded into the buckets");
+
+    let gauge = find(&scope.metrics, "in_flight")
+        .data
+        .as_any()
+        .downcast_ref::<Gauge<f64>>()
+        .expect("gauge bridged as a gauge");
+    assert_eq!(gauge.data_points[0].value, 7.0);
+}
+
+#[test]
+fn label_values_are_unescaped() {
+    let bridge = bridge_text(concat!(
+        "# TYPE jobs counter\n",
+        "jobs_total{name=\"a \\\"quoted\\\" \\\\ name\",queue=\"x,y}\"} 2\n",
+        "# EOF\n",
+    ));
+    let scope = bridge.produce().expect("produce");
+    let sum = find(&scope.metrics, "jobs")
+        .data
+        .as_any()
+        .downcast_ref::<Sum<f64>>()
+        .expect("sum");
+    let point = &sum.data_points[0];
+    let attrs: Vec<_> = point
+        .attributes
+        .iter()
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    assert_eq!(
+        attr(attrs.clone().into_iter(), "name").as_deref(),
+        Some("a \"quoted\" \\ name")
+    );
+    assert_eq!(attr(attrs.into_iter(), "queue").as_deref(), Some("x,y}"));
+    assert_eq!(point.value, 2.0);
+}
+
+#[test]
+fn unsupported_types_are_skipped_and_malformed_text_is_an_error() {
+    let scope = bridge_text("# TYPE build info\nbuild_info{version=\"1\"} 1\n")
+        .produce()
+        .expect("produce");
+    assert!(scope.metrics.is_empty());
+
+    assert!(
+        bridge_text("# TYPE jobs counter\njobs_total{name=\"x} 1\n")
+            .produce()
+            .is_err()
+    );
+}
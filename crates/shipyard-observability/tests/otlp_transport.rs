@@ -0,0 +1,89 @@
+//! `init` installs process-wide state: keep this binary to a single test.
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+use shipyard_observability::{
+    DEFAULT_SHUTDOWN_TIMEOUT, ExporterConfig, ObservabilityConfig, SamplingConfig,
+    exporter::Protocol,
+};
+
+/// One-shot OTLP/HTTP collector: returns the request line and lowercased headers.
+fn collector() -> (String, mpsc::Receiver<(String, Vec<String>)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).unwrap();
+        let mut headers = Vec::new();
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let line = line.trim_end().to_ascii_lowercase();
+            if line.is_empty() {
+                break;
+            }
+            headers.push(line);
+        }
+        let length = headers
+            .iter()
+            .find_map(|h| h.strip_prefix("content-length: "))
+            .map_or(0, |n| n.parse().unwrap());
+        reader.read_exact(&mut vec![0; length]).unwrap();
+        reader
+            .get_mut()
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+            .unwrap();
+        tx.send((request_line.trim_end().to_string(), headers))
+            .unwrap();
+    });
+    (endpoint, rx)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn http_protobuf_export_sends_signal_path_and_headers() {
+    let (endpoint, requests) = collector();
+    let telemetry = shipyard_observability::init(ObservabilityConfig {
+        service_name: "otlp-transport-test".into(),
+        service_version: None,
+        deployment_environment: None,
+        resource_attributes: Vec::new(),
+        otlp_endpoint: Some(endpoint),
+        exporter: ExporterConfig {
+            protocol: Protocol::HttpProtobuf,
+            headers: vec![("Authorization".into(), "Bearer s3cret".into())],
+            ..Default::default()
+        },
+        log_output: Default::default(),
+        log_filter: Some("info".into()),
+        metrics: None,
+        sampling: SamplingConfig::default(),
+        export_logs: false,
+        shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+    })
+    .unwrap();
+
+    tracing::info_span!("exported").in_scope(|| {});
+    tokio::task::spawn_blocking(move || telemetry.shutdown())
+        .await
+        .unwrap();
+
+    let (request_line, headers) = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(request_line, "POST /v1/traces HTTP/1.1");
+    assert!(
+        headers.contains(&"authorization: bearer s3cret".to_string()),
+        "{headers:?}"
+    );
+    assert!(
+        headers.contains(&"content-type: application/x-protobuf".to_string()),
+        "{headers:?}"
+    );
+}
@@ -0,0 +1,82 @@
+use opentelemetry::{Key, Value};
+use shipyard_observability::{
+    DEFAULT_SHUTDOWN_TIMEOUT, ObservabilityConfig, SamplingConfig, resource,
+};
+
+fn config(resource_attributes: &[(&str, &str)]) -> ObservabilityConfig {
+    ObservabilityConfig {
+        service_name: "fulfilment-api".into(),
+        service_version: Some("1.2.3".into()),
+        deployment_environment: Some("prod".into()),
+        resource_attributes: resource_attributes
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect(),
+        otlp_endpoint: None,
+        exporter: Default::default(),
+        log_output: Default::default(),
+        log_filter: None,
+        metrics: None,
+        sampling: SamplingConfig::default(),
+        export_logs: false,
+        shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+    }
+}
+
+fn get(resource: &opentelemetry_sdk::Resource, key: &'static str) -> Option<String> {
+    resource
+        .get(Key::from_static_str(key))
+        .map(|v: Value| v.to_string())
+}
+
+#[test]
+fn resource_carries_configured_and_detected_attributes() {
+    let resource = resource(&config(&[("team", "fulfilment")]));
+
+    assert_eq!(
+        get(&resource, "service.name").as_deref(),
+        Some("fulfilment-api")
+    );
+    assert_eq!(get(&resource, "service.version").as_deref(), Some("1.2.3"));
+    assert_eq!(
+        get(&resource, "deployment.environment").as_deref(),
+        Some("prod")
+    );
+    assert_eq!(get(&resource, "team").as_deref(), Some("fulfilment"));
+    assert_eq!(
+        get(&resource, "telemetry.sdk.language").as_deref(),
+        Some("rust")
+    );
+    assert_eq!(
+        get(&resource, "process.pid"),
+        Some(std::process::id().to_string())
+    );
+    for key in ["service.instance.id", "host.arch", "os.type"] {
+        assert!(get(&resource, key).is_some(), "{key} missing");
+    }
+}
+
+#[test]
+fn resource_attributes_override_configured_but_not_service_name() {
+    let resource = resource(&config(&[
+        ("deployment.environment", "staging"),
+        ("service.name", "other"),
+    ]));
+
+    assert_eq!(
+        get(&resource, "deployment.environment").as_deref(),
+        Some("staging")
+    );
+    assert_eq!(
+        get(&resource, "service.name").as_deref(),
+        Some("fulfilment-api")
+    );
+}
+
+#[test]
+fn instance_id_is_a_fresh_uuid_per_resource() {
+    let a = get(&resource(&config(&[])), "service.instance.id").unwrap();
+    let b = get(&resource(&config(&[])), "service.instance.id").unwrap();
+    assert_ne!(a, b);
+    assert_eq!(a.len(), 36);
+}
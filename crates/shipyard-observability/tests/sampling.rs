@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use opentelemetry::{
+    Context, KeyValue,
+    trace::{SamplingDecision, SpanKind, TraceId, TraceResult, TracerProvider as _},
+};
+use opentelemetry_sdk::{
+    export::trace::SpanData,
+    trace::{ShouldSample, Span, SpanProcessor, TracerProvider, config},
+};
+use shipyard_observability::{Sampler, SamplingConfig, sampling::KeepErrors};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Stands in for the batch exporter: keeps sampled spans only.
+#[derive(Debug, Clone, Default)]
+struct Exported(Arc<Mutex<Vec<String>>>);
+
+impl SpanProcessor for Exported {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+    fn on_end(&self, span: SpanData) {
+        if span.span_context.is_sampled() {
+            self.0.lock().unwrap().push(span.name.to_string());
+        }
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        Ok(())
+    }
+}
+
+fn decision(sampling: &SamplingConfig, attributes: &[KeyValue]) -> SamplingDecision {
+    sampling
+        .sampler()
+        .should_sample(
+            None,
+            TraceId::from(42u128),
+            "http.request",
+            &SpanKind::Internal,
+            attributes,
+            &[],
+        )
+        .decision
+}
+
+/// Runs `f` under a subscriber exporting through `sampling`; returns exported span names.
+fn exported(sampling: &SamplingConfig, f: impl FnOnce()) -> Vec<String> {
+    let exported = Exported::default();
+    let provider = TracerProvider::builder()
+        .with_config(config().with_sampler(sampling.sampler()))
+        .with_span_processor(KeepErrors::new(exported.clone()))
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    tracing::subscriber::with_default(subscriber, f);
+    drop(provider);
+
+    exported.0.lock().unwrap().clone()
+}
+
+#[test]
+fn samplers_parse_from_standard_env_values() {
+    let parent = |inner| Sampler::ParentBased(Box::new(inner));
+    let cases = [
+        ("always_on", None, Sampler::AlwaysOn),
+        ("always_off", None, Sampler::AlwaysOff),
+        ("traceidratio", Some(0.25), Sampler::TraceIdRatio(0.25)),
+        ("traceidratio", None, Sampler::TraceIdRatio(1.0)),
+        ("parentbased_always_on", None, parent(Sampler::AlwaysOn)),
+        ("parentbased_always_off", None, parent(Sampler::AlwaysOff)),
+        (
+            "parentbased_traceidratio",
+            Some(0.1),
+            parent(Sampler::TraceIdRatio(0.1)),
+        ),
+    ];
+    for (name, arg, expected) in cases {
+        assert_eq!(Sampler::from_otel(name, arg).unwrap(), expected, "{name}");
+    }
+    assert_eq!(Sampler::default(), parent(Sampler::AlwaysOn));
+
+    assert!(Sampler::from_otel("traceidratio", Some(1.5)).is_err());
+    assert!(Sampler::from_otel("jaeger_remote", None).is_err());
+}
+
+#[test]
+fn keep_routes_override_the_head_decision() {
+    let sampling = SamplingConfig {
+        sampler: Sampler::AlwaysOff,
+        keep_errors: false,
+        keep_routes: vec!["/api/v1/orders".to_string()],
+    };
+
+    assert_eq!(
+        decision(
+            &sampling,
+            &[KeyValue::new("path", "/api/v1/orders/validate")]
+        ),
+        SamplingDecision::RecordAndSample
+    );
+    assert_eq!(
+        decision(&sampling, &[KeyValue::new("path", "/healthz")]),
+        SamplingDecision::Drop
+    );
+
+    let names = exported(&sampling, || {
+        tracing::info_span!("kept", path = "/api/v1/orders").in_scope(|| {});
+        tracing::info_span!("dropped", path = "/readyz").in_scope(|| {});
+    });
+    assert_eq!(names, vec!["kept"]);
+}
+
+#[test]
+fn keep_errors_exports_failed_spans_that_were_not_sampled() {
+    let sampling = SamplingConfig {
+        sampler: Sampler::AlwaysOff,
+        keep_errors: true,
+        keep_routes: Vec::new(),
+    };
+    assert_eq!(
+        decision(&sampling, &[]),
+        SamplingDecision::RecordOnly,
+        "recorded so the final status is known"
+    );
+
+    let names = exported(&sampling, || {
+        tracing::info_span!("ok").in_scope(|| tracing::info!("fine"));
+        tracing::info_span!("failed").in_scope(|| tracing::error!("db unavailable"));
+    });
+    assert_eq!(names, vec!["failed"]);
+}
+
+#[test]
+fn without_rules_the_head_decision_stands() {
+    let off = SamplingConfig {
+        sampler: Sampler::AlwaysOff,
+        ..SamplingConfig::default()
+    };
+    let names = exported(&off, || {
+        tracing::info_span!("failed").in_scope(|| tracing::error!("db unavailable"));
+    });
+    assert!(names.is_empty());
+
+    let names = exported(&SamplingConfig::default(), || {
+        tracing::info_span!("ok").in_scope(|| {});
+    });
+    assert_eq!(names, vec!["ok"]);
+}
@@ -0,0 +1,92 @@
+use shipyard_observability::testing;
+use tracing::Level;
+
+#[test]
+fn captures_span_tree_fields_and_logs() {
+    let telemetry = testing::capture();
+
+    let request = tracing::info_span!(
+        "http.request",
+        request_id = "req-1",
+        tenant_id = tracing::field::Empty,
+    );
+    request.record("tenant_id", "acme");
+    request.in_scope(|| {
+        tracing::info_span!("orders.create", request_id = "req-1").in_scope(|| {
+            tracing::info_span!("db.query").in_scope(|| tracing::warn!(rows = 0, "order.missing"));
+        });
+        tracing::info!(status = 201, "request.completed");
+    });
+
+    let create = telemetry
+        .spans()
+        .named("orders.create")
+        .with_field("request_id", "req-1")
+        .child_of("http.request")
+        .one();
+    let parent = telemetry.parent(&create).unwrap();
+    assert_eq!(parent.field("tenant_id"), Some("acme"), "recorded later");
+    assert_eq!(create.trace_id, parent.trace_id);
+    assert_ne!(create.span_id, parent.span_id);
+    assert!(create.trace_id.is_some());
+
+    assert!(
+        telemetry
+            .spans()
+            .named("db.query")
+            .within("http.request")
+            .exists()
+    );
+    assert!(
+        !telemetry
+            .spans()
+            .named("db.query")
+            .child_of("http.request")
+            .exists()
+    );
+
+    let missing = telemetry
+        .logs()
+        .message("order.missing")
+        .level(Level::WARN)
+        .in_span("orders.create")
+        .one();
+    assert_eq!(missing.field("rows"), Some("0"));
+    assert_eq!(telemetry.span_of(&missing).unwrap().name, "db.query");
+
+    telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("status", 201)
+        .in_span("http.request")
+        .one();
+    assert!(
+        !telemetry
+            .logs()
+            .message("request.completed")
+            .in_span("orders.create")
+            .exists()
+    );
+}
+
+#[test]
+fn capture_is_scoped_to_the_telemetry_value() {
+    let first = testing::capture();
+    tracing::info!("first");
+    drop(first);
+
+    tracing::info!("not captured");
+
+    let second = testing::capture();
+    tracing::info!("second");
+    assert_eq!(second.logs().count(), 1);
+    assert!(second.logs().message("second").exists());
+}
+
+#[test]
+#[should_panic(expected = "expected one span named `missing`, found 0")]
+fn one_panics_with_what_was_captured() {
+    let telemetry = testing::capture();
+    tracing::info_span!("present").in_scope(|| {});
+    telemetry.spans().named("missing").one();
+}
@@ -0,0 +1,91 @@
+use std::{fmt, sync::Arc};
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{HeaderValue, header},
+    middleware::{Next, from_fn_with_state},
+    response::{IntoResponse, Response},
+};
+
+use crate::{ApiError, RequestId};
+
+/// Who may call `/admin/*` routes: holders of one shared bearer token.
+///
+/// Without a token (the default) admin routes are disabled and answer `403`.
+#[derive(Clone, Default)]
+pub struct AdminAuth {
+    token: Option<Arc<str>>,
+}
+
+impl AdminAuth {
+    pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Require `Authorization: Bearer <token>`.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self {
+            token: Some(token.into().into()),
+        }
+    }
+}
+
+impl fmt::Debug for AdminAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdminAuth")
+            .field("enabled", &self.token.is_some())
+            .finish()
+    }
+}
+
+/// Guard every route of `router` with `auth`.
+///
+/// A route layer: unmatched paths still get the regular 404.
+pub fn protect_admin<S>(router: Router<S>, auth: AdminAuth) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.route_layer(from_fn_with_state(auth, admin_auth_middleware))
+}
+
+/// Middleware: `403 FORBIDDEN` when admin is disabled, `401 UNAUTHORIZED` without the token.
+pub async fn admin_auth_middleware(
+    State(auth): State<AdminAuth>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let req_id = req
+        .extensions()
+        .get::<RequestId>()
+        .cloned()
+        .unwrap_or_default();
+
+    let Some(expected) = &auth.token else {
+        return ApiError::forbidden(&req_id, "Admin API is disabled (no admin token configured)")
+            .into_response();
+    };
+
+    let presented = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "));
+
+    match presented {
+        Some(token) if constant_time_eq(token.trim().as_bytes(), expected.as_bytes()) => {
+            next.run(req).await
+        }
+        _ => {
+            let mut res = ApiError::unauthorized(&req_id).into_response();
+            res.headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            res
+        }
+    }
+}
+
+/// Compare without short-circuiting, so timing does not reveal the matching prefix.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -1,22 +1,55 @@
-use axum::{Router, extract::Extension, middleware::from_fn};
+use axum::{
+    Router,
+    extract::Extension,
+    middleware::{from_fn, from_fn_with_state},
+};
 use tower_http::trace::TraceLayer;
 
-use crate::{ApiError, RequestId, request_id_middleware, request_log_middleware};
+use crate::{
+    ApiError, Catalogue, Maintenance, RequestId, TenantConfig, localize_middleware,
+    maintenance_middleware, request_id_middleware, request_log_middleware, tenant_middleware,
+};
+
+/// Tunables for the web contract. `Default` is the golden path.
+#[derive(Clone, Debug, Default)]
+pub struct WebContractConfig {
+    pub tenant: TenantConfig,
+    pub maintenance: Maintenance,
+    /// Error message translations, negotiated from `Accept-Language`.
+    pub messages: Catalogue,
+}
 
 /// Apply the standard Shipyard web contract to a router.
 ///
 /// Contract:
 /// - `x-request-id` is always present on responses
 /// - every request has a span carrying `request_id`, `trace_id`, `span_id`
+/// - the tenant (if any) is resolved and recorded on the span and access log
+/// - maintenance mode rejects requests with a `503 MAINTENANCE` envelope + `Retry-After`
 /// - 404 returns standard JSON error envelope including request_id
+/// - error messages are localised from `Accept-Language` (English fallback, codes unchanged)
 pub fn apply_web_contract<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    apply_web_contract_with(router, WebContractConfig::default())
+}
+
+/// Same as `apply_web_contract`, with explicit settings.
+pub fn apply_web_contract_with<S>(router: Router<S>, cfg: WebContractConfig) -> Router<S>
 where
     S: Clone + Send + Sync + 'static,
 {
     router
         .fallback(not_found)
-        // inside span: can read RequestId extension AND Span::current has OTEL context
+        // innermost: rejections still get the access log, span and x-request-id
+        .layer(from_fn_with_state(cfg.maintenance, maintenance_middleware))
+        // translates ApiError messages, including maintenance rejections and the 404 fallback
+        .layer(from_fn_with_state(cfg.messages, localize_middleware))
+        // inside span: can read RequestId/TenantId extensions AND Span::current has OTEL context
         .layer(from_fn(request_log_middleware))
+        // inside span: resolves TenantId and records it on the span
+        .layer(from_fn_with_state(cfg.tenant, tenant_middleware))
         // creates `http.request` span using RequestId extension
         .layer(trace_layer())
         // outermost: runs first, inserts RequestId into extensions + sets x-request-id header
@@ -39,6 +72,9 @@ fn trace_layer() -> TraceLayer<
             request_id = %req_id,
             method = %req.method(),
             path = %req.uri().path(),
+            tenant_id = tracing::field::Empty,
+            // Set to `ERROR` on 5xx by `request_log_middleware`.
+            otel.status_code = tracing::field::Empty,
         )
     })
 }
@@ -0,0 +1,163 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use axum::{
+    Router,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue},
+    middleware::{Next, from_fn_with_state},
+    response::Response,
+};
+
+use crate::TenantId;
+
+const CLIENT_ID_HEADER: &str = "x-client-id";
+const MAX_CLIENT_LEN: usize = 64;
+
+/// Deprecation metadata for a route or nested router.
+///
+/// Responses carry:
+/// - `Deprecation: @<unix-seconds>` (RFC 9745)
+/// - `Sunset: <HTTP-date>` (RFC 8594), if set
+/// - `Link: <url>; rel="deprecation"`, if set
+#[derive(Clone, Debug)]
+pub struct Deprecation {
+    deprecation: HeaderValue,
+    sunset: Option<HeaderValue>,
+    link: Option<HeaderValue>,
+}
+
+impl Deprecation {
+    /// `deprecated_on` is a `YYYY-MM-DD` date (UTC).
+    ///
+    /// Panics on an invalid date: intended for literals at router build time.
+    pub fn new(deprecated_on: &str) -> Self {
+        let at = parse_date(deprecated_on);
+        let secs = at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+        Self {
+            deprecation: HeaderValue::from_str(&format!("@{secs}")).expect("valid header"),
+            sunset: None,
+            link: None,
+        }
+    }
+
+    /// `sunset_on` is a `YYYY-MM-DD` date (UTC). Panics on an invalid date.
+    pub fn sunset(mut self, sunset_on: &str) -> Self {
+        let at = parse_date(sunset_on);
+        self.sunset =
+            Some(HeaderValue::from_str(&httpdate::fmt_http_date(at)).expect("valid header"));
+        self
+    }
+
+    /// Docs describing the deprecation and migration path. Panics on a non-header-safe URL.
+    pub fn link(mut self, url: &str) -> Self {
+        self.link = Some(
+            HeaderValue::from_str(&format!("<{url}>; rel=\"deprecation\""))
+                .expect("deprecation link must be a valid header value"),
+        );
+        self
+    }
+}
+
+/// Marker inserted into response extensions for calls to deprecated routes.
+///
+/// Services read it in their metrics middleware to count usage per client.
+#[derive(Clone, Debug)]
+pub struct DeprecatedCall {
+    /// Tenant, `x-client-id`, or the `user-agent` product token (else `unknown`).
+    pub client: String,
+}
+
+/// Mark every route in `router` as deprecated.
+///
+/// Works for nested routers (`deprecate(v1::router(), ..)`) and single routes
+/// (`deprecate(Router::new().route(..), ..)` merged into the parent).
+pub fn deprecate<S>(router: Router<S>, deprecation: Deprecation) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router.layer(from_fn_with_state(deprecation, deprecation_middleware))
+}
+
+async fn deprecation_middleware(
+    State(dep): State<Deprecation>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let client = client_id(&req);
+
+    let mut res = next.run(req).await;
+
+    let headers = res.headers_mut();
+    headers.insert("deprecation", dep.deprecation.clone());
+    if let Some(sunset) = &dep.sunset {
+        headers.insert("sunset", sunset.clone());
+    }
+    if let Some(link) = &dep.link {
+        headers.append("link", link.clone());
+    }
+
+    res.extensions_mut().insert(DeprecatedCall { client });
+    res
+}
+
+fn client_id(req: &Request) -> String {
+    if let Some(tenant) = req.extensions().get::<TenantId>() {
+        return tenant.0.clone();
+    }
+
+    header_str(req.headers(), CLIENT_ID_HEADER)
+        .or_else(|| {
+            header_str(req.headers(), "user-agent")
+                .and_then(|ua| ua.split(['/', ' ']).next())
+                .filter(|s| !s.is_empty())
+        })
+        .map(|s| s.chars().take(MAX_CLIENT_LEN).collect())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn parse_date(raw: &str) -> SystemTime {
+    try_parse_date(raw).unwrap_or_else(|| panic!("invalid date (expected YYYY-MM-DD): {raw}"))
+}
+
+fn try_parse_date(raw: &str) -> Option<SystemTime> {
+    let mut parts = raw.trim().splitn(3, '-');
+    let y: i64 = parts.next()?.parse().ok()?;
+    let m: u32 = parts.next()?.parse().ok()?;
+    let d: u32 = parts.next()?.parse().ok()?;
+
+    if y < 1970 || !(1..=12).contains(&m) || d == 0 || d > days_in_month(y, m) {
+        return None;
+    }
+
+    let days = days_from_civil(y, m, d) as u64;
+    Some(UNIX_EPOCH + Duration::from_secs(days * 86_400))
+}
+
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 if (y % 4 == 0 && y % 100 != 0) || y % 400 == 0 => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date (Howard Hinnant's algorithm).
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = i64::from((m + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
@@ -6,15 +6,18 @@ use axum::{
 use serde::Serialize;
 use serde_json::Value;
 
-use crate::middleware::RequestId;
+use crate::{
+    i18n::{LocalizableError, MessageKey},
+    middleware::RequestId,
+};
 
 /// Standard error response envelope.
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ErrorEnvelope {
     pub error: ErrorBody,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 pub struct ErrorBody {
     pub code: &'static str,
     pub message: String,
@@ -35,6 +38,10 @@ pub struct ApiError {
     pub message: String,
     pub request_id: String,
     pub details: Option<Value>,
+    /// Catalogue key for localising `message`; `None` means "use `code`".
+    /// Boxed to keep `Result<_, ApiError>` small. Crate-private (so other crates cannot build
+    /// `ApiError` literals that break on new fields): use `with_message_key` / `message_key()`.
+    pub(crate) message_key: Option<Box<MessageKey>>,
 }
 
 impl ApiError {
@@ -45,6 +52,7 @@ impl ApiError {
             message: message.into(),
             request_id: req_id.0.clone(),
             details: None,
+            message_key: None,
         }
     }
 
@@ -55,6 +63,29 @@ impl ApiError {
             message: "Route not found".to_string(),
             request_id: req_id.0.clone(),
             details: None,
+            message_key: None,
+        }
+    }
+
+    pub fn unauthorized(req_id: &RequestId) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            code: "UNAUTHORIZED",
+            message: "Missing or invalid credentials".to_string(),
+            request_id: req_id.0.clone(),
+            details: None,
+            message_key: None,
+        }
+    }
+
+    pub fn forbidden(req_id: &RequestId, message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::FORBIDDEN,
+            code: "FORBIDDEN",
+            message: message.into(),
+            request_id: req_id.0.clone(),
+            details: None,
+            message_key: None,
         }
     }
 
@@ -65,6 +96,7 @@ impl ApiError {
             message: "Internal server error".to_string(),
             request_id: req_id.0.clone(),
             details: None,
+            message_key: None,
         }
     }
 
@@ -75,6 +107,7 @@ impl ApiError {
             message: message.into(),
             request_id: req_id.0.clone(),
             details: None,
+            message_key: None,
         }
     }
 
@@ -83,6 +116,26 @@ impl ApiError {
         self
     }
 
+    /// Set the catalogue key (and template parameters) used to localise `message`.
+    ///
+    /// `message` stays the English text; see `Catalogue`.
+    pub fn with_message_key(
+        mut self,
+        key: &'static str,
+        params: impl IntoIterator<Item = (&'static str, String)>,
+    ) -> Self {
+        self.message_key = Some(Box::new(MessageKey {
+            key,
+            params: params.into_iter().collect(),
+        }));
+        self
+    }
+
+    /// Catalogue key set by `with_message_key`, if any.
+    pub fn message_key(&self) -> Option<&MessageKey> {
+        self.message_key.as_deref()
+    }
+
     pub fn from_status(req_id: &RequestId, status: StatusCode) -> Self {
         match status {
             StatusCode::BAD_REQUEST => Self {
@@ -91,6 +144,7 @@ impl ApiError {
                 message: "Bad request".to_string(),
                 request_id: req_id.0.clone(),
                 details: None,
+                message_key: None,
             },
             StatusCode::NOT_FOUND => Self::not_found(req_id),
             StatusCode::INTERNAL_SERVER_ERROR => Self::internal(req_id),
@@ -100,6 +154,7 @@ impl ApiError {
                 message: "Request failed".to_string(),
                 request_id: req_id.0.clone(),
                 details: None,
+                message_key: None,
             },
         }
     }
@@ -116,6 +171,20 @@ impl IntoResponse for ApiError {
             },
         };
 
-        (self.status, Json(env)).into_response()
+        let key = self.message_key.map_or(
+            MessageKey {
+                key: self.code,
+                params: Vec::new(),
+            },
+            |k| *k,
+        );
+        let localizable = LocalizableError {
+            key,
+            envelope: env.clone(),
+        };
+
+        let mut res = (self.status, Json(env)).into_response();
+        res.extensions_mut().insert(localizable);
+        res
     }
 }
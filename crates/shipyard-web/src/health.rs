@@ -0,0 +1,246 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Extension, Json,
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use futures_util::future::join_all;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::{Maintenance, MaintenanceMode, Readiness};
+
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(1);
+
+pub type CheckResult = Result<(), String>;
+type CheckFuture = Pin<Box<dyn Future<Output = CheckResult> + Send>>;
+type CheckFn = Arc<dyn Fn() -> CheckFuture + Send + Sync>;
+type CachedReports = Option<(Instant, Vec<CheckReport>)>;
+
+/// A named readiness check.
+///
+/// Critical checks fail readiness (503); non-critical checks are reported only.
+#[derive(Clone)]
+pub struct HealthCheck {
+    name: &'static str,
+    timeout: Duration,
+    critical: bool,
+    run: CheckFn,
+}
+
+impl HealthCheck {
+    pub fn critical<F, Fut>(name: &'static str, timeout: Duration, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CheckResult> + Send + 'static,
+    {
+        Self::new(name, timeout, true, f)
+    }
+
+    pub fn non_critical<F, Fut>(name: &'static str, timeout: Duration, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CheckResult> + Send + 'static,
+    {
+        Self::new(name, timeout, false, f)
+    }
+
+    fn new<F, Fut>(name: &'static str, timeout: Duration, critical: bool, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = CheckResult> + Send + 'static,
+    {
+        Self {
+            name,
+            timeout,
+            critical,
+            run: Arc::new(move || Box::pin(f())),
+        }
+    }
+
+    async fn execute(&self) -> CheckReport {
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(self.timeout, (self.run)()).await;
+        let latency_ms = start.elapsed().as_millis() as u64;
+
+        let (status, error) = match outcome {
+            Ok(Ok(())) => (CheckStatus::Ok, None),
+            Ok(Err(e)) => (CheckStatus::Fail, Some(e)),
+            Err(_) => (
+                CheckStatus::Timeout,
+                Some(format!("timed out after {}ms", self.timeout.as_millis())),
+            ),
+        };
+
+        CheckReport {
+            name: self.name,
+            status,
+            critical: self.critical,
+            latency_ms,
+            error,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    Ok,
+    Fail,
+    Timeout,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckReport {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub critical: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Structured readiness output (served as JSON by `readyz`).
+#[derive(Clone, Debug, Serialize)]
+pub struct HealthReport {
+    pub ready: bool,
+    pub draining: bool,
+    pub maintenance: MaintenanceMode,
+    pub checks: Vec<CheckReport>,
+}
+
+/// Registry of readiness checks.
+///
+/// Behaviour:
+/// - checks run concurrently, each bounded by its own timeout
+/// - results are cached for a short TTL so probes can't stampede dependencies
+/// - readiness fails if any critical check fails, or the server is draining
+#[derive(Clone)]
+pub struct HealthRegistry {
+    checks: Arc<Vec<HealthCheck>>,
+    cache_ttl: Duration,
+    cache: Arc<Mutex<CachedReports>>,
+}
+
+impl Default for HealthRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        Self {
+            checks: Arc::new(Vec::new()),
+            cache_ttl: DEFAULT_CACHE_TTL,
+            cache: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    pub fn register(mut self, check: HealthCheck) -> Self {
+        Arc::make_mut(&mut self.checks).push(check);
+        self
+    }
+
+    /// Run (or reuse cached) checks and build a report.
+    ///
+    /// Maintenance mode is reported but does not fail readiness: clients should
+    /// keep reaching the service and get `503 MAINTENANCE` envelopes.
+    pub async fn report(
+        &self,
+        readiness: Option<&Readiness>,
+        maintenance: Option<&Maintenance>,
+    ) -> HealthReport {
+        let checks = self.run_checks().await;
+        let draining = readiness.is_some_and(|r| !r.is_ready());
+        let maintenance = maintenance.map_or(MaintenanceMode::Off, Maintenance::mode);
+        let ready = !draining
+            && checks
+                .iter()
+                .all(|c| !c.critical || c.status == CheckStatus::Ok);
+
+        HealthReport {
+            ready,
+            draining,
+            maintenance,
+            checks,
+        }
+    }
+
+    async fn run_checks(&self) -> Vec<CheckReport> {
+        // Held across the run so concurrent probes share one refresh.
+        let mut cache = self.cache.lock().await;
+
+        if let Some((at, reports)) = cache.as_ref()
+            && at.elapsed() < self.cache_ttl
+        {
+            return reports.clone();
+        }
+
+        let reports = join_all(self.checks.iter().map(HealthCheck::execute)).await;
+
+        for r in reports.iter().filter(|r| r.status != CheckStatus::Ok) {
+            tracing::warn!(
+                check = r.name,
+                critical = r.critical,
+                status = ?r.status,
+                error = r.error.as_deref().unwrap_or(""),
+                "health.check_failed"
+            );
+        }
+
+        *cache = Some((Instant::now(), reports.clone()));
+        reports
+    }
+}
+
+/// Handler: GET /readyz
+///
+/// - plain `ready` / `not ready` body by default (simple probes)
+/// - JSON `HealthReport` when the client sends `Accept: application/json`
+///
+/// Requires `Extension<HealthRegistry>`; picks up `Readiness` when served via `serve`.
+pub async fn readyz(
+    Extension(health): Extension<HealthRegistry>,
+    readiness: Option<Extension<Readiness>>,
+    maintenance: Option<Extension<Maintenance>>,
+    headers: HeaderMap,
+) -> Response {
+    let report = health
+        .report(
+            readiness.as_ref().map(|r| &r.0),
+            maintenance.as_ref().map(|m| &m.0),
+        )
+        .await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    if wants_json(&headers) {
+        (status, Json(report)).into_response()
+    } else if report.ready {
+        (status, "ready").into_response()
+    } else {
+        (status, "not ready").into_response()
+    }
+}
+
+fn wants_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("application/json"))
+}
@@ -0,0 +1,199 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+use crate::ErrorEnvelope;
+
+/// Locale of the messages built into `ApiError` constructors.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Catalogue key + parameters for an error message.
+///
+/// Templates use `{name}` placeholders filled from `params`.
+#[derive(Clone, Debug)]
+pub struct MessageKey {
+    pub key: &'static str,
+    pub params: Vec<(&'static str, String)>,
+}
+
+/// Carried in response extensions by `ApiError::into_response` so `localize_middleware`
+/// can re-render the envelope without parsing the body.
+#[derive(Clone, Debug)]
+pub(crate) struct LocalizableError {
+    pub(crate) key: MessageKey,
+    pub(crate) envelope: ErrorEnvelope,
+}
+
+/// Translated message templates keyed by (locale, message key).
+///
+/// English is the source language: `ApiError.message` is used as-is when
+/// no translation exists. Keys default to the error `code` (e.g. `NOT_FOUND`).
+#[derive(Clone, Debug)]
+pub struct Catalogue {
+    entries: Arc<HashMap<(&'static str, &'static str), &'static str>>,
+}
+
+impl Default for Catalogue {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl Catalogue {
+    /// Empty catalogue (English only).
+    pub fn empty() -> Self {
+        Self {
+            entries: Arc::new(HashMap::new()),
+        }
+    }
+
+    /// Translations for the codes emitted by shipyard-web itself.
+    pub fn builtin() -> Self {
+        Self::empty().with_entries(&[
+            ("de", "NOT_FOUND", "Route nicht gefunden"),
+            ("pl", "NOT_FOUND", "Nie znaleziono ścieżki"),
+            ("de", "INTERNAL_ERROR", "Interner Serverfehler"),
+            ("pl", "INTERNAL_ERROR", "Wewnętrzny błąd serwera"),
+            ("de", "BAD_REQUEST", "Ungültige Anfrage"),
+            ("pl", "BAD_REQUEST", "Nieprawidłowe żądanie"),
+            ("de", "VALIDATION_ERROR", "Die Anfrage ist ungültig"),
+            ("pl", "VALIDATION_ERROR", "Żądanie jest nieprawidłowe"),
+            (
+                "de",
+                "CONFLICT",
+                "Konflikt mit dem aktuellen Zustand der Ressource",
+            ),
+            ("pl", "CONFLICT", "Konflikt z bieżącym stanem zasobu"),
+            ("de", "UNAUTHORIZED", "Fehlende oder ungültige Anmeldedaten"),
+            (
+                "pl",
+                "UNAUTHORIZED",
+                "Brak lub nieprawidłowe dane uwierzytelniające",
+            ),
+            ("de", "FORBIDDEN", "Zugriff verweigert"),
+            ("pl", "FORBIDDEN", "Odmowa dostępu"),
+            ("de", "ERROR", "Anfrage fehlgeschlagen"),
+            ("pl", "ERROR", "Żądanie nie powiodło się"),
+            ("de", "TENANT_REQUIRED", "Mandant ist erforderlich"),
+            (
+                "pl",
+                "TENANT_REQUIRED",
+                "Wymagany jest identyfikator najemcy",
+            ),
+            ("de", "INVALID_TENANT", "Ungültiger Mandant"),
+            ("pl", "INVALID_TENANT", "Nieprawidłowy najemca"),
+            (
+                "de",
+                "MAINTENANCE",
+                "Der Dienst befindet sich im Wartungsmodus ({mode})",
+            ),
+            (
+                "pl",
+                "MAINTENANCE",
+                "Usługa jest w trybie konserwacji ({mode})",
+            ),
+        ])
+    }
+
+    /// Add (or override) translations: `(locale, key, template)`.
+    pub fn with_entries(mut self, entries: &[(&'static str, &'static str, &'static str)]) -> Self {
+        let map = Arc::make_mut(&mut self.entries);
+        for (locale, key, template) in entries {
+            map.insert((*locale, *key), *template);
+        }
+        self
+    }
+
+    /// Render `key` in `locale`, or `None` if there is no translation.
+    pub fn render(&self, locale: &str, key: &MessageKey) -> Option<String> {
+        let entries: &HashMap<(&str, &str), &str> = &self.entries;
+        let template = entries.get(&(locale, key.key))?;
+
+        let mut out = template.to_string();
+        for (name, value) in &key.params {
+            out = out.replace(&format!("{{{name}}}"), value);
+        }
+        Some(out)
+    }
+
+    fn supports(&self, locale: &str) -> bool {
+        locale == DEFAULT_LOCALE || self.entries.keys().any(|(l, _)| *l == locale)
+    }
+
+    /// Pick the best supported locale from an `Accept-Language` value.
+    ///
+    /// Matches on the primary subtag (`de-AT` → `de`); falls back to English.
+    pub fn negotiate(&self, accept_language: &str) -> String {
+        let mut ranges: Vec<(String, f32)> = accept_language
+            .split(',')
+            .filter_map(|part| {
+                let mut it = part.trim().split(';');
+                let tag = it.next()?.trim();
+                let q = it
+                    .find_map(|p| p.trim().strip_prefix("q="))
+                    .and_then(|q| q.parse::<f32>().ok())
+                    .unwrap_or(1.0);
+                let primary = tag.split('-').next()?.to_ascii_lowercase();
+                (!primary.is_empty() && q > 0.0).then_some((primary, q))
+            })
+            .collect();
+
+        // Stable sort keeps header order for equal weights.
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges
+            .into_iter()
+            .map(|(tag, _)| tag)
+            .find(|tag| self.supports(tag))
+            .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+    }
+}
+
+/// Middleware: translate `ApiError` messages according to `Accept-Language`.
+///
+/// Only `error.message` changes; `error.code` and everything else stay stable.
+/// Localised responses carry `Content-Language`.
+pub async fn localize_middleware(
+    State(catalogue): State<Catalogue>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let locale = req
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| catalogue.negotiate(v))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+
+    let res = next.run(req).await;
+
+    if locale == DEFAULT_LOCALE {
+        return res;
+    }
+
+    let Some(err) = res.extensions().get::<LocalizableError>().cloned() else {
+        return res;
+    };
+    let Some(message) = catalogue.render(&locale, &err.key) else {
+        return res;
+    };
+
+    let mut envelope = err.envelope;
+    envelope.error.message = message;
+    let Ok(body) = serde_json::to_vec(&envelope) else {
+        return res;
+    };
+
+    let (mut parts, _) = res.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    if let Ok(v) = HeaderValue::from_str(&locale) {
+        parts.headers.insert(header::CONTENT_LANGUAGE, v);
+    }
+    Response::from_parts(parts, Body::from(body))
+}
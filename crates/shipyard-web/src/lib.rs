@@ -5,18 +5,42 @@
 //! Provides:
 //! - Request correlation (`x-request-id`) via middleware
 //! - A consistent JSON error envelope (`ApiError`)
+//! - Error message localisation from `Accept-Language` (codes stay stable)
+//! - Tenant resolution (`TenantId` extension + extractor)
+//! - A runtime maintenance switch (read-only / full) with an admin endpoint
+//! - A shared bearer token guarding `/admin/*` routes
+//! - Route deprecation (`Deprecation` / `Sunset` / `Link` headers)
 //! - A golden-path helper to apply the standard web contract to a router
+//! - A health check registry backing `/readyz` (plain or JSON breakdown)
+//! - A graceful server runner (readiness flip, pre-stop delay, bounded drain)
 //!
 //! Non-goals:
 //! - Tracing/metrics export (belongs in shipyard-observability)
-//! - Auth, sessions, validation frameworks
+//! - Auth beyond the admin token, sessions, validation frameworks
 
+pub mod admin;
 pub mod contract;
+pub mod deprecation;
 pub mod error;
+pub mod health;
+pub mod i18n;
+pub mod maintenance;
 pub mod middleware;
 pub mod request_log;
+pub mod serve;
+pub mod tenant;
 
-pub use contract::{apply_web_contract, not_found};
+pub use admin::{AdminAuth, admin_auth_middleware, protect_admin};
+pub use contract::{WebContractConfig, apply_web_contract, apply_web_contract_with, not_found};
+pub use deprecation::{DeprecatedCall, Deprecation, deprecate};
 pub use error::{ApiError, ErrorBody, ErrorEnvelope};
+pub use health::{HealthCheck, HealthRegistry, HealthReport};
+pub use i18n::{Catalogue, MessageKey, localize_middleware};
+pub use maintenance::{
+    MAINTENANCE_ADMIN_PATH, Maintenance, MaintenanceMode, maintenance_admin_router,
+    maintenance_middleware,
+};
 pub use middleware::{RequestId, request_id_middleware};
 pub use request_log::request_log_middleware;
+pub use serve::{Readiness, ServeConfig, serve, serve_with_shutdown, shutdown_signal};
+pub use tenant::{TenantConfig, TenantId, tenant_middleware};
@@ -0,0 +1,238 @@
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{
+    Extension, Json, Router,
+    extract::{Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+
+use crate::{AdminAuth, ApiError, RequestId, protect_admin};
+
+/// Path of the admin endpoint mounted by `maintenance_admin_router`.
+pub const MAINTENANCE_ADMIN_PATH: &str = "/admin/maintenance";
+
+/// Paths served regardless of maintenance mode.
+const EXEMPT_PATHS: &[&str] = &["/healthz", "/readyz", "/metrics", MAINTENANCE_ADMIN_PATH];
+
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MaintenanceMode {
+    Off,
+    /// Reject mutating methods (POST/PUT/PATCH/DELETE).
+    ReadOnly,
+    /// Reject everything except health, metrics and the admin endpoint.
+    Full,
+}
+
+impl MaintenanceMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaintenanceMode::Off => "off",
+            MaintenanceMode::ReadOnly => "read_only",
+            MaintenanceMode::Full => "full",
+        }
+    }
+
+    /// Parse the file-watch format (`off` | `read_only` | `full`); empty means off.
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim() {
+            "" | "off" => Some(MaintenanceMode::Off),
+            "read_only" => Some(MaintenanceMode::ReadOnly),
+            "full" => Some(MaintenanceMode::Full),
+            _ => None,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            MaintenanceMode::Off => 0,
+            MaintenanceMode::ReadOnly => 1,
+            MaintenanceMode::Full => 2,
+        }
+    }
+
+    fn from_u8(v: u8) -> Self {
+        match v {
+            1 => MaintenanceMode::ReadOnly,
+            2 => MaintenanceMode::Full,
+            _ => MaintenanceMode::Off,
+        }
+    }
+
+    fn rejects(&self, method: &Method, path: &str) -> bool {
+        if EXEMPT_PATHS.contains(&path) {
+            return false;
+        }
+        match self {
+            MaintenanceMode::Off => false,
+            MaintenanceMode::ReadOnly => {
+                !matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+            }
+            MaintenanceMode::Full => true,
+        }
+    }
+}
+
+/// Runtime-togglable maintenance switch shared by the middleware, admin endpoint and file watch.
+#[derive(Clone, Debug)]
+pub struct Maintenance {
+    mode: Arc<AtomicU8>,
+    retry_after: Duration,
+}
+
+impl Default for Maintenance {
+    fn default() -> Self {
+        Self::new(DEFAULT_RETRY_AFTER)
+    }
+}
+
+impl Maintenance {
+    pub fn new(retry_after: Duration) -> Self {
+        Self {
+            mode: Arc::new(AtomicU8::new(MaintenanceMode::Off.to_u8())),
+            retry_after,
+        }
+    }
+
+    pub fn mode(&self) -> MaintenanceMode {
+        MaintenanceMode::from_u8(self.mode.load(Ordering::SeqCst))
+    }
+
+    pub fn set(&self, mode: MaintenanceMode) {
+        let prev = MaintenanceMode::from_u8(self.mode.swap(mode.to_u8(), Ordering::SeqCst));
+        if prev != mode {
+            tracing::warn!(
+                from = prev.as_str(),
+                to = mode.as_str(),
+                "maintenance.mode_changed"
+            );
+        }
+    }
+
+    /// Poll `path` every `interval` and apply its contents (`off` | `read_only` | `full`).
+    ///
+    /// Only changes are applied, so a mode set through the admin endpoint stays until the
+    /// file is written or removed. Removing the file ends maintenance (once); a file that is
+    /// missing from the start changes nothing.
+    pub fn watch_file(&self, path: PathBuf, interval: Duration) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            // Last content seen; `None` while the file does not exist.
+            let mut last: Option<String> = None;
+            loop {
+                match tokio::fs::read_to_string(&path).await {
+                    Ok(raw) if last.as_deref() == Some(raw.as_str()) => {}
+                    Ok(raw) => {
+                        match MaintenanceMode::parse(&raw) {
+                            Some(mode) => this.set(mode),
+                            None => tracing::warn!(
+                                path = %path.display(),
+                                value = raw.trim(),
+                                "maintenance.file_invalid"
+                            ),
+                        }
+                        last = Some(raw);
+                    }
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                        if last.take().is_some() {
+                            this.set(MaintenanceMode::Off);
+                        }
+                    }
+                    Err(e) => tracing::warn!(
+                        path = %path.display(),
+                        error = %e,
+                        "maintenance.file_unreadable"
+                    ),
+                }
+                tokio::time::sleep(interval).await;
+            }
+        })
+    }
+}
+
+/// Middleware: reject requests according to the current maintenance mode.
+///
+/// Rejections use the standard envelope (`503 MAINTENANCE`) plus `Retry-After`.
+/// Also inserts `Maintenance` into extensions for `/readyz` and the admin endpoint.
+pub async fn maintenance_middleware(
+    State(maintenance): State<Maintenance>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let mode = maintenance.mode();
+
+    if mode.rejects(req.method(), req.uri().path()) {
+        let req_id = req
+            .extensions()
+            .get::<RequestId>()
+            .cloned()
+            .unwrap_or_default();
+
+        let mut res = ApiError {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            code: "MAINTENANCE",
+            message: format!("Service is in maintenance ({})", mode.as_str()),
+            request_id: req_id.0,
+            details: None,
+            message_key: None,
+        }
+        .with_message_key("MAINTENANCE", [("mode", mode.as_str().to_string())])
+        .into_response();
+
+        res.headers_mut().insert(
+            header::RETRY_AFTER,
+            HeaderValue::from(maintenance.retry_after.as_secs()),
+        );
+        return res;
+    }
+
+    req.extensions_mut().insert(maintenance);
+    next.run(req).await
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MaintenanceStatus {
+    pub mode: MaintenanceMode,
+}
+
+/// Admin endpoint: `GET`/`PUT` `/admin/maintenance` with `{"mode": "read_only"}`.
+///
+/// Guarded by `auth` (see `protect_admin`).
+pub fn maintenance_admin_router<S>(auth: AdminAuth) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    protect_admin(
+        Router::new().route(
+            MAINTENANCE_ADMIN_PATH,
+            get(get_maintenance).put(put_maintenance),
+        ),
+        auth,
+    )
+}
+
+async fn get_maintenance(Extension(m): Extension<Maintenance>) -> Json<MaintenanceStatus> {
+    Json(MaintenanceStatus { mode: m.mode() })
+}
+
+async fn put_maintenance(
+    Extension(m): Extension<Maintenance>,
+    Json(body): Json<MaintenanceStatus>,
+) -> Json<MaintenanceStatus> {
+    m.set(body.mode);
+    Json(MaintenanceStatus { mode: m.mode() })
+}
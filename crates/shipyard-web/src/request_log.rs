@@ -4,14 +4,17 @@ use std::time::Instant;
 use tracing::{Level, Span};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-use crate::RequestId;
+use crate::{RequestId, TenantId};
 
 /// Canonical request log with correlation fields.
 ///
 /// Emits exactly one log event per request:
-/// - request_id (from extensions)
+/// - request_id, tenant_id (from extensions)
 /// - trace_id/span_id (from current OTEL context)
 /// - method/path/status/latency
+///
+/// Also marks the request span as failed on 5xx (`otel.status_code = "ERROR"`), so
+/// error traces are exported even when sampling dropped them (`keep_errors`).
 pub async fn request_log_middleware(req: Request, next: Next) -> Response {
     let start = Instant::now();
 
@@ -21,18 +24,28 @@ pub async fn request_log_middleware(req: Request, next: Next) -> Response {
         .map(|r| r.0.clone())
         .unwrap_or_default();
 
+    let tenant_id = req
+        .extensions()
+        .get::<TenantId>()
+        .map(|t| t.0.clone())
+        .unwrap_or_default();
+
     let method = req.method().as_str().to_string();
     let path = req.uri().path().to_string();
 
     let res = next.run(req).await;
 
+    let span = Span::current();
+    if res.status().is_server_error() {
+        span.record("otel.status_code", "ERROR");
+    }
+
     if path == "/metrics" {
         return res;
     }
 
     let latency = start.elapsed();
 
-    let span = Span::current();
     let cx = span.context();
     let otel_span = cx.span();
     let sc = otel_span.span_context();
@@ -51,6 +64,7 @@ pub async fn request_log_middleware(req: Request, next: Next) -> Response {
     tracing::event!(
         Level::INFO,
         request_id = %req_id,
+        tenant_id = %tenant_id,
         trace_id = %trace_id,
         span_id = %span_id,
         method = %method,
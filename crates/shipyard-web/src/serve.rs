@@ -0,0 +1,155 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{Extension, Router};
+use tokio::{net::TcpListener, sync::oneshot};
+
+/// Shared readiness flag flipped by `serve` when shutdown begins.
+///
+/// `serve` installs it as a request extension, so `/readyz` handlers can
+/// extract `Option<Extension<Readiness>>` and report 503 while draining.
+#[derive(Clone, Debug, Default)]
+pub struct Readiness {
+    draining: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_ready(&self) -> bool {
+        !self.draining.load(Ordering::SeqCst)
+    }
+
+    pub fn mark_draining(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Shutdown timings for `serve`.
+#[derive(Clone, Debug)]
+pub struct ServeConfig {
+    /// Time between the shutdown signal and closing the listener.
+    /// `/readyz` reports 503 during this window so load balancers stop routing.
+    pub pre_stop_delay: Duration,
+
+    /// Hard deadline for in-flight requests once the listener is closed.
+    pub drain_timeout: Duration,
+}
+
+impl Default for ServeConfig {
+    fn default() -> Self {
+        Self {
+            pre_stop_delay: Duration::from_secs(5),
+            drain_timeout: Duration::from_secs(25),
+        }
+    }
+}
+
+/// Serve `app` until SIGTERM/SIGINT, then shut down gracefully.
+///
+/// Shutdown sequence:
+/// 1. flip readiness (`/readyz` → 503)
+/// 2. wait `pre_stop_delay` while still serving traffic
+/// 3. stop accepting and drain in-flight requests, bounded by `drain_timeout`
+/// 4. run `flush` (e.g. shut down the `shipyard_observability::ObservabilityGuard`)
+pub async fn serve<F>(
+    listener: TcpListener,
+    app: Router,
+    readiness: Readiness,
+    cfg: ServeConfig,
+    flush: F,
+) -> std::io::Result<()>
+where
+    F: FnOnce(),
+{
+    serve_with_shutdown(listener, app, readiness, cfg, shutdown_signal(), flush).await
+}
+
+/// Like `serve`, but shutdown starts when `signal` resolves (useful for tests).
+pub async fn serve_with_shutdown<S, F>(
+    listener: TcpListener,
+    app: Router,
+    readiness: Readiness,
+    cfg: ServeConfig,
+    signal: S,
+    flush: F,
+) -> std::io::Result<()>
+where
+    S: Future<Output = ()> + Send + 'static,
+    F: FnOnce(),
+{
+    let app = app.layer(Extension(readiness.clone()));
+
+    let (draining_tx, draining_rx) = oneshot::channel::<()>();
+    let pre_stop_delay = cfg.pre_stop_delay;
+
+    let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+        signal.await;
+
+        readiness.mark_draining();
+        tracing::info!(
+            pre_stop_delay_ms = pre_stop_delay.as_millis() as u64,
+            "server.draining"
+        );
+        tokio::time::sleep(pre_stop_delay).await;
+
+        let _ = draining_tx.send(());
+    });
+
+    let deadline = async move {
+        // Only start the clock once the listener has been closed.
+        if draining_rx.await.is_err() {
+            return std::future::pending().await;
+        }
+        tokio::time::sleep(cfg.drain_timeout).await;
+    };
+
+    let result = tokio::select! {
+        res = server => res,
+        _ = deadline => {
+            tracing::warn!(
+                drain_timeout_ms = cfg.drain_timeout.as_millis() as u64,
+                "server.drain_timeout; dropping in-flight requests"
+            );
+            Ok(())
+        }
+    };
+
+    tracing::info!("server.stopped");
+    flush();
+
+    result
+}
+
+/// Resolves on SIGTERM or SIGINT (Ctrl-C on non-unix platforms).
+pub async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+
+        let mut term = signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
+        let mut int = signal(SignalKind::interrupt()).expect("failed to install SIGINT handler");
+
+        tokio::select! {
+            _ = term.recv() => {
+                tracing::info!("shutdown signal received (SIGTERM)");
+            }
+            _ = int.recv() => {
+                tracing::info!("shutdown signal received (SIGINT)");
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+        tracing::info!("shutdown signal received");
+    }
+}
@@ -0,0 +1,161 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{HeaderName, StatusCode, request::Parts},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Span;
+
+use crate::{ApiError, RequestId};
+
+const DEFAULT_TENANT_HEADER: &str = "x-tenant-id";
+const MAX_TENANT_ID_LEN: usize = 64;
+
+/// Tenant the request is acting for.
+///
+/// Resolution order (see `tenant_middleware`):
+/// 1. a `TenantId` already in extensions (inserted by an auth layer from the principal)
+/// 2. the configured tenant header (default `x-tenant-id`)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TenantId(pub String);
+
+impl TenantId {
+    /// Validate a raw tenant id: 1..=64 chars of `[A-Za-z0-9_-]`.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let s = raw.trim();
+        let valid = !s.is_empty()
+            && s.len() <= MAX_TENANT_ID_LEN
+            && s.bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+
+        valid.then(|| TenantId(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Tenant resolution settings.
+#[derive(Clone, Debug)]
+pub struct TenantConfig {
+    /// Header carrying the tenant id when no principal provides one.
+    pub header: HeaderName,
+
+    /// If set, only these tenant ids are accepted.
+    pub allowed: Option<Arc<HashSet<String>>>,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            header: HeaderName::from_static(DEFAULT_TENANT_HEADER),
+            allowed: None,
+        }
+    }
+}
+
+/// Why a tenant could not be resolved; read by the `TenantId` extractor.
+#[derive(Clone, Debug)]
+enum TenantRejection {
+    Invalid(String),
+}
+
+/// Middleware: resolve the tenant and record it on the current (`http.request`) span.
+///
+/// Never rejects on its own: routes that need a tenant use the `TenantId`
+/// extractor, so health/metrics endpoints stay tenant-free.
+///
+/// The resolved tenant is also copied into the response extensions, for layers that
+/// wrap the contract (e.g. per-tenant HTTP metrics).
+pub async fn tenant_middleware(
+    State(cfg): State<TenantConfig>,
+    mut req: Request,
+    next: Next,
+) -> Response {
+    let resolved = match req.extensions().get::<TenantId>() {
+        Some(t) => Some(Ok(t.clone())),
+        None => req
+            .headers()
+            .get(&cfg.header)
+            .map(|raw| resolve_header(&cfg, raw.to_str().unwrap_or_default())),
+    };
+
+    let tenant = match resolved {
+        Some(Ok(tenant)) => {
+            Span::current().record("tenant_id", tenant.as_str());
+            req.extensions_mut().insert(tenant.clone());
+            Some(tenant)
+        }
+        Some(Err(rejection)) => {
+            req.extensions_mut().insert(rejection);
+            None
+        }
+        None => None,
+    };
+
+    let mut res = next.run(req).await;
+    if let Some(tenant) = tenant {
+        res.extensions_mut().insert(tenant);
+    }
+    res
+}
+
+fn resolve_header(cfg: &TenantConfig, raw: &str) -> Result<TenantId, TenantRejection> {
+    let tenant = TenantId::parse(raw).ok_or_else(|| {
+        TenantRejection::Invalid(format!(
+            "{} must be 1-{MAX_TENANT_ID_LEN} characters of [A-Za-z0-9_-]",
+            cfg.header
+        ))
+    })?;
+
+    if let Some(allowed) = &cfg.allowed
+        && !allowed.contains(tenant.as_str())
+    {
+        return Err(TenantRejection::Invalid("unknown tenant".to_string()));
+    }
+
+    Ok(tenant)
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for TenantId
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(tenant) = parts.extensions.get::<TenantId>() {
+            return Ok(tenant.clone());
+        }
+
+        let req_id = parts
+            .extensions
+            .get::<RequestId>()
+            .cloned()
+            .unwrap_or_default();
+
+        match parts.extensions.get::<TenantRejection>() {
+            Some(TenantRejection::Invalid(message)) => Err(ApiError {
+                status: StatusCode::BAD_REQUEST,
+                code: "INVALID_TENANT",
+                message: message.clone(),
+                request_id: req_id.0,
+                details: None,
+                message_key: None,
+            }),
+            None => Err(ApiError {
+                status: StatusCode::BAD_REQUEST,
+                code: "TENANT_REQUIRED",
+                message: "tenant is required".to_string(),
+                request_id: req_id.0,
+                details: None,
+                message_key: None,
+            }),
+        }
+    }
+}
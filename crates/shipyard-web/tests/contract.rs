@@ -6,6 +6,7 @@ use axum::{
 };
 use http_body_util::BodyExt;
 use serde_json::Value;
+use shipyard_observability::testing;
 use tower::ServiceExt;
 
 fn app() -> Router {
@@ -107,3 +108,56 @@ async fn contract_is_state_generic() {
     assert_eq!(res.status(), StatusCode::OK);
     assert!(!header_str(&res, "x-request-id").trim().is_empty());
 }
+
+#[tokio::test]
+async fn request_span_and_access_log_carry_correlation_fields() {
+    let telemetry = testing::capture();
+    let request = Request::builder()
+        .uri("/ok")
+        .header("x-request-id", "fixed-id-span")
+        .header("x-tenant-id", "acme")
+        .body(Body::empty())
+        .unwrap();
+    let res = app().oneshot(request).await.expect("oneshot");
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let span = telemetry
+        .spans()
+        .named("http.request")
+        .with_field("request_id", "fixed-id-span")
+        .with_field("method", "GET")
+        .with_field("path", "/ok")
+        .with_field("tenant_id", "acme")
+        .one();
+
+    let log = telemetry
+        .logs()
+        .message("request.completed")
+        .in_span("http.request")
+        .one();
+    assert_eq!(log.field("request_id"), Some("fixed-id-span"));
+    assert_eq!(log.field("tenant_id"), Some("acme"));
+    assert_eq!(log.field("status"), Some("200"));
+    assert!(span.trace_id.is_some(), "{span:?}");
+    assert_eq!(log.field("trace_id"), span.trace_id.as_deref());
+    assert_eq!(log.field("span_id"), span.span_id.as_deref());
+}
+
+#[tokio::test]
+async fn rejected_requests_are_still_logged_once_with_their_status() {
+    let telemetry = testing::capture();
+    let res = app()
+        .oneshot(req("/does-not-exist", Some("fixed-id-miss")))
+        .await
+        .expect("oneshot");
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+    telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("request_id", "fixed-id-miss")
+        .with_field("status", 404)
+        .with_field("path", "/does-not-exist")
+        .in_span("http.request")
+        .one();
+}
@@ -0,0 +1,81 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use shipyard_web::{DeprecatedCall, Deprecation, deprecate};
+use tower::ServiceExt;
+
+fn app() -> Router {
+    let legacy = deprecate(
+        Router::new().route("/things", get(|| async { "old" })),
+        Deprecation::new("2026-11-01")
+            .sunset("2027-05-01")
+            .link("https://docs.example.com/migrate-v2"),
+    );
+
+    shipyard_web::apply_web_contract(
+        Router::new()
+            .nest("/api/v1", legacy)
+            .route("/api/v2/things", get(|| async { "new" })),
+    )
+}
+
+fn req(uri: &str, headers: &[(&str, &str)]) -> Request<Body> {
+    let mut builder = Request::builder().method("GET").uri(uri);
+    for (k, v) in headers {
+        builder = builder.header(*k, *v);
+    }
+    builder.body(Body::empty()).expect("build request")
+}
+
+#[tokio::test]
+async fn deprecated_routes_carry_headers() {
+    let res = app().oneshot(req("/api/v1/things", &[])).await.unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    // 2026-11-01T00:00:00Z
+    assert_eq!(res.headers()["deprecation"], "@1793491200");
+    assert_eq!(res.headers()["sunset"], "Sat, 01 May 2027 00:00:00 GMT");
+    assert_eq!(
+        res.headers()["link"],
+        "<https://docs.example.com/migrate-v2>; rel=\"deprecation\""
+    );
+    assert!(res.headers().contains_key("x-request-id"));
+}
+
+#[tokio::test]
+async fn other_routes_are_untouched() {
+    let res = app().oneshot(req("/api/v2/things", &[])).await.unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(!res.headers().contains_key("deprecation"));
+    assert!(res.extensions().get::<DeprecatedCall>().is_none());
+}
+
+#[tokio::test]
+async fn deprecated_call_identifies_client() {
+    let res = app()
+        .oneshot(req("/api/v1/things", &[("user-agent", "warehouse-ui/3.2")]))
+        .await
+        .unwrap();
+    let call = res.extensions().get::<DeprecatedCall>().expect("marker");
+    assert_eq!(call.client, "warehouse-ui");
+
+    let res = app()
+        .oneshot(req(
+            "/api/v1/things",
+            &[("x-client-id", "billing"), ("x-tenant-id", "acme")],
+        ))
+        .await
+        .unwrap();
+    let call = res.extensions().get::<DeprecatedCall>().expect("marker");
+    assert_eq!(call.client, "acme");
+}
+
+#[test]
+#[should_panic(expected = "invalid date")]
+fn invalid_date_panics_at_build_time() {
+    Deprecation::new("2026-02-30");
+}
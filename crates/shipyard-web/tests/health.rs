@@ -0,0 +1,145 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use shipyard_web::{HealthCheck, HealthRegistry, Readiness};
+use tower::ServiceExt;
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+fn app(health: HealthRegistry) -> Router {
+    Router::new()
+        .route("/readyz", get(shipyard_web::health::readyz))
+        .layer(Extension(health))
+}
+
+async fn readyz_json(app: Router) -> (StatusCode, Value) {
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/readyz")
+                .header("accept", "application/json")
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await
+        .expect("oneshot");
+
+    let status = res.status();
+    let bytes = res
+        .into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes();
+    (status, serde_json::from_slice(&bytes).expect("parse json"))
+}
+
+#[tokio::test]
+async fn plain_body_when_all_checks_pass() {
+    let health =
+        HealthRegistry::new().register(HealthCheck::critical("db", TIMEOUT, || async { Ok(()) }));
+
+    let res = app(health)
+        .oneshot(Request::get("/readyz").body(Body::empty()).unwrap())
+        .await
+        .expect("oneshot");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&bytes[..], b"ready");
+}
+
+#[tokio::test]
+async fn critical_failure_is_not_ready() {
+    let health = HealthRegistry::new()
+        .register(HealthCheck::critical("db", TIMEOUT, || async {
+            Err("connection refused".to_string())
+        }))
+        .register(HealthCheck::non_critical("cache", TIMEOUT, || async {
+            Ok(())
+        }));
+
+    let (status, v) = readyz_json(app(health)).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(v["ready"], false);
+    assert_eq!(v["checks"][0]["name"], "db");
+    assert_eq!(v["checks"][0]["status"], "fail");
+    assert_eq!(v["checks"][0]["error"], "connection refused");
+    assert!(v["checks"][0]["latency_ms"].is_u64());
+    assert_eq!(v["checks"][1]["status"], "ok");
+}
+
+#[tokio::test]
+async fn non_critical_failure_stays_ready() {
+    let health = HealthRegistry::new()
+        .register(HealthCheck::critical("db", TIMEOUT, || async { Ok(()) }))
+        .register(HealthCheck::non_critical("backlog", TIMEOUT, || async {
+            Err("too many pending".to_string())
+        }));
+
+    let (status, v) = readyz_json(app(health)).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["ready"], true);
+    assert_eq!(v["checks"][1]["status"], "fail");
+}
+
+#[tokio::test]
+async fn slow_check_times_out() {
+    let health = HealthRegistry::new().register(HealthCheck::critical("slow", TIMEOUT, || async {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        Ok(())
+    }));
+
+    let (status, v) = readyz_json(app(health)).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(v["checks"][0]["status"], "timeout");
+}
+
+#[tokio::test]
+async fn results_are_cached_within_ttl() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let health = HealthRegistry::new()
+        .with_cache_ttl(Duration::from_secs(60))
+        .register(HealthCheck::critical("db", TIMEOUT, move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+            async { Ok(()) }
+        }));
+
+    let app = app(health);
+    readyz_json(app.clone()).await;
+    readyz_json(app).await;
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn draining_is_not_ready() {
+    let readiness = Readiness::new();
+    readiness.mark_draining();
+
+    let health =
+        HealthRegistry::new().register(HealthCheck::critical("db", TIMEOUT, || async { Ok(()) }));
+    let app = app(health).layer(Extension(readiness));
+
+    let (status, v) = readyz_json(app).await;
+
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(v["draining"], true);
+}
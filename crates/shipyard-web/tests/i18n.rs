@@ -0,0 +1,131 @@
+use axum::{
+    Router,
+    body::Body,
+    extract::Extension,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use shipyard_web::{ApiError, Catalogue, MessageKey, RequestId, WebContractConfig};
+use tower::ServiceExt;
+
+async fn qty(Extension(req_id): Extension<RequestId>) -> ApiError {
+    ApiError::validation(&req_id, "items[2].qty must be > 0")
+        .with_message_key("items.qty", [("idx", "2".to_string())])
+}
+
+fn app() -> Router {
+    shipyard_web::apply_web_contract_with(
+        Router::new().route("/qty", get(qty)),
+        WebContractConfig {
+            messages: Catalogue::builtin().with_entries(&[(
+                "de",
+                "items.qty",
+                "items[{idx}].qty muss > 0 sein",
+            )]),
+            ..WebContractConfig::default()
+        },
+    )
+}
+
+async fn call(uri: &str, accept_language: Option<&str>) -> (StatusCode, Option<String>, Value) {
+    let mut builder = Request::builder().method("GET").uri(uri);
+    if let Some(lang) = accept_language {
+        builder = builder.header("accept-language", lang);
+    }
+    let res = app()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    let status = res.status();
+    let lang = res
+        .headers()
+        .get("content-language")
+        .map(|v| v.to_str().unwrap().to_string());
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    (status, lang, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn translates_message_and_keeps_code() {
+    let (status, lang, v) = call("/qty", Some("de-AT")).await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(lang.as_deref(), Some("de"));
+    assert_eq!(v["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(v["error"]["message"], "items[2].qty muss > 0 sein");
+    assert!(!v["error"]["request_id"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn builtin_codes_are_translated() {
+    let (status, _, v) = call("/nope", Some("pl")).await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(v["error"]["code"], "NOT_FOUND");
+    assert_eq!(v["error"]["message"], "Nie znaleziono ścieżki");
+}
+
+#[tokio::test]
+async fn falls_back_to_english() {
+    let (_, lang, v) = call("/qty", None).await;
+    assert_eq!(lang, None);
+    assert_eq!(v["error"]["message"], "items[2].qty must be > 0");
+
+    // Polish is supported in general, but has no template for this key.
+    let (_, lang, v) = call("/qty", Some("pl")).await;
+    assert_eq!(lang, None);
+    assert_eq!(v["error"]["message"], "items[2].qty must be > 0");
+}
+
+#[test]
+fn negotiation_honours_q_values() {
+    let catalogue = Catalogue::builtin();
+
+    assert_eq!(catalogue.negotiate("fr, pl;q=0.5, de;q=0.8"), "de");
+    assert_eq!(catalogue.negotiate("de;q=0, pl;q=0.1"), "pl");
+    assert_eq!(catalogue.negotiate("fr-CA, es"), "en");
+    assert_eq!(catalogue.negotiate("EN-gb, de"), "en");
+}
+
+#[test]
+fn every_builtin_code_has_german_and_polish() {
+    let req_id = RequestId("rid".to_string());
+    let catalogue = Catalogue::builtin();
+
+    for err in [
+        ApiError::validation(&req_id, "bad"),
+        ApiError::not_found(&req_id),
+        ApiError::unauthorized(&req_id),
+        ApiError::forbidden(&req_id, "no"),
+        ApiError::internal(&req_id),
+        ApiError::conflict(&req_id, "dup"),
+        ApiError::from_status(&req_id, StatusCode::BAD_REQUEST),
+        ApiError::from_status(&req_id, StatusCode::IM_A_TEAPOT),
+    ] {
+        assert!(err.message_key().is_none());
+        let key = MessageKey {
+            key: err.code,
+            params: Vec::new(),
+        };
+        for locale in ["de", "pl"] {
+            assert!(
+                catalogue.render(locale, &key).is_some(),
+                "{locale} {}",
+                err.code
+            );
+        }
+    }
+}
+
+#[test]
+fn message_key_is_read_through_the_accessor() {
+    let err = ApiError::validation(&RequestId("rid".to_string()), "bad")
+        .with_message_key("items.qty", [("idx", "2".to_string())]);
+
+    let key = err.message_key().expect("key set");
+    assert_eq!(key.key, "items.qty");
+    assert_eq!(key.params, vec![("idx", "2".to_string())]);
+}
@@ -0,0 +1,107 @@
+use std::sync::{Arc, Mutex};
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use opentelemetry::{
+    Context, Value,
+    trace::{TraceResult, TracerProvider as _},
+};
+use opentelemetry_sdk::{
+    export::trace::SpanData,
+    trace::{Span, SpanProcessor, TracerProvider, config},
+};
+use shipyard_observability::{Sampler, SamplingConfig, sampling::KeepErrors, testing};
+use shipyard_web::{ApiError, RequestId};
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Stands in for the batch exporter: keeps the `path` of sampled spans only.
+#[derive(Debug, Clone, Default)]
+struct Exported(Arc<Mutex<Vec<String>>>);
+
+impl SpanProcessor for Exported {
+    fn on_start(&self, _span: &mut Span, _cx: &Context) {}
+
+    fn on_end(&self, span: SpanData) {
+        if !span.span_context.is_sampled() {
+            return;
+        }
+        let path = span
+            .attributes
+            .iter()
+            .find(|kv| kv.key.as_str() == "path")
+            .map(|kv| kv.value.clone())
+            .unwrap_or(Value::from(""));
+        self.0.lock().unwrap().push(path.to_string());
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        Ok(())
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        Ok(())
+    }
+}
+
+async fn boom(Extension(req_id): Extension<RequestId>) -> ApiError {
+    ApiError::internal(&req_id)
+}
+
+fn app() -> Router {
+    shipyard_web::apply_web_contract(
+        Router::new()
+            .route("/ok", get(|| async { "ok" }))
+            .route("/boom", get(boom)),
+    )
+}
+
+#[tokio::test]
+async fn server_errors_are_exported_when_sampling_drops_the_request() {
+    let sampling = SamplingConfig {
+        sampler: Sampler::AlwaysOff,
+        keep_errors: true,
+        keep_routes: Vec::new(),
+    };
+    let exported = Exported::default();
+    let provider = TracerProvider::builder()
+        .with_config(config().with_sampler(sampling.sampler()))
+        .with_span_processor(KeepErrors::new(exported.clone()))
+        .build();
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+    let guard = tracing::subscriber::set_default(subscriber);
+
+    for (path, status) in [
+        ("/ok", StatusCode::OK),
+        ("/boom", StatusCode::INTERNAL_SERVER_ERROR),
+    ] {
+        let req = Request::builder().uri(path).body(Body::empty()).unwrap();
+        let res = app().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), status);
+    }
+
+    drop(guard);
+    drop(provider);
+    assert_eq!(*exported.0.lock().unwrap(), vec!["/boom".to_string()]);
+}
+
+#[tokio::test]
+async fn only_server_errors_mark_the_request_span_as_failed() {
+    let telemetry = testing::capture();
+    for path in ["/ok", "/missing", "/boom"] {
+        let req = Request::builder().uri(path).body(Body::empty()).unwrap();
+        app().oneshot(req).await.unwrap();
+    }
+
+    let failed = telemetry
+        .spans()
+        .named("http.request")
+        .with_field("otel.status_code", "ERROR")
+        .one();
+    assert_eq!(failed.field("path"), Some("/boom"));
+}
@@ -0,0 +1,218 @@
+use std::time::Duration;
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use shipyard_web::{
+    AdminAuth, HealthRegistry, Maintenance, MaintenanceMode, WebContractConfig,
+    maintenance_admin_router,
+};
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token-0123456789";
+
+fn app(maintenance: Maintenance) -> Router {
+    app_with_auth(maintenance, AdminAuth::bearer(ADMIN_TOKEN))
+}
+
+fn app_with_auth(maintenance: Maintenance, auth: AdminAuth) -> Router {
+    shipyard_web::apply_web_contract_with(
+        Router::new()
+            .route("/healthz", get(|| async { "ok" }))
+            .route("/readyz", get(shipyard_web::health::readyz))
+            .route(
+                "/things",
+                get(|| async { "list" }).post(|| async { "created" }),
+            )
+            .merge(maintenance_admin_router(auth))
+            .layer(Extension(HealthRegistry::new())),
+        WebContractConfig {
+            maintenance,
+            ..WebContractConfig::default()
+        },
+    )
+}
+
+fn req(method: &str, uri: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("accept", "application/json")
+        .header("content-type", "application/json")
+        .body(Body::empty())
+        .expect("build request")
+}
+
+fn put_mode(mode: &str, token: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder()
+        .method("PUT")
+        .uri("/admin/maintenance")
+        .header("content-type", "application/json");
+    if let Some(token) = token {
+        builder = builder.header("authorization", format!("Bearer {token}"));
+    }
+    builder
+        .body(Body::from(format!(r#"{{"mode":"{mode}"}}"#)))
+        .unwrap()
+}
+
+async fn json_body(res: axum::response::Response) -> Value {
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&bytes).expect("parse json body")
+}
+
+#[tokio::test]
+async fn off_by_default() {
+    let res = app(Maintenance::default())
+        .oneshot(req("POST", "/things"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn read_only_rejects_writes_with_envelope_and_retry_after() {
+    let m = Maintenance::new(Duration::from_secs(120));
+    m.set(MaintenanceMode::ReadOnly);
+    let app = app(m);
+
+    let res = app.clone().oneshot(req("GET", "/things")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let res = app.oneshot(req("POST", "/things")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(res.headers()["retry-after"], "120");
+    assert!(res.headers().contains_key("x-request-id"));
+
+    let v = json_body(res).await;
+    assert_eq!(v["error"]["code"], "MAINTENANCE");
+    assert!(!v["error"]["request_id"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn full_rejects_everything_but_runtime_endpoints() {
+    let m = Maintenance::default();
+    m.set(MaintenanceMode::Full);
+    let app = app(m);
+
+    let res = app.clone().oneshot(req("GET", "/things")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let res = app.clone().oneshot(req("GET", "/healthz")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+
+    // Maintenance is reported, but readiness stays green.
+    let res = app.oneshot(req("GET", "/readyz")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(json_body(res).await["maintenance"], "full");
+}
+
+#[tokio::test]
+async fn admin_endpoint_toggles_mode() {
+    let m = Maintenance::default();
+    m.set(MaintenanceMode::Full);
+    let app = app(m.clone());
+
+    let res = app
+        .clone()
+        .oneshot(put_mode("off", Some(ADMIN_TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(json_body(res).await["mode"], "off");
+    assert_eq!(m.mode(), MaintenanceMode::Off);
+
+    let res = app.oneshot(req("POST", "/things")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn file_watch_applies_mode() {
+    let path = std::env::temp_dir().join(format!("maintenance-{}", uuid::Uuid::new_v4()));
+    std::fs::write(&path, "read_only\n").unwrap();
+
+    let m = Maintenance::default();
+    let handle = m.watch_file(path.clone(), Duration::from_millis(20));
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(m.mode(), MaintenanceMode::ReadOnly);
+
+    std::fs::remove_file(&path).unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(m.mode(), MaintenanceMode::Off);
+
+    handle.abort();
+}
+
+#[tokio::test]
+async fn file_watch_only_applies_changes() {
+    let path = std::env::temp_dir().join(format!("maintenance-{}", uuid::Uuid::new_v4()));
+    let m = Maintenance::default();
+    let handle = m.watch_file(path.clone(), Duration::from_millis(20));
+
+    // Set through the admin endpoint while no file exists: survives several polls.
+    let res = app(m.clone())
+        .oneshot(put_mode("full", Some(ADMIN_TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(m.mode(), MaintenanceMode::Full);
+
+    // Unchanged file content does not override a later admin change.
+    std::fs::write(&path, "read_only\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(m.mode(), MaintenanceMode::ReadOnly);
+    m.set(MaintenanceMode::Full);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(m.mode(), MaintenanceMode::Full);
+
+    std::fs::remove_file(&path).unwrap();
+    handle.abort();
+}
+
+#[tokio::test]
+async fn admin_endpoint_requires_the_admin_token() {
+    let m = Maintenance::default();
+
+    for token in [None, Some("wrong-token")] {
+        let res = app(m.clone())
+            .oneshot(put_mode("full", token))
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED, "{token:?}");
+        assert_eq!(res.headers()["www-authenticate"], "Bearer");
+        assert_eq!(json_body(res).await["error"]["code"], "UNAUTHORIZED");
+    }
+    let res = app(m.clone())
+        .oneshot(req("GET", "/admin/maintenance"))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(m.mode(), MaintenanceMode::Off);
+
+    let res = app(m.clone())
+        .oneshot(put_mode("full", Some(ADMIN_TOKEN)))
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(m.mode(), MaintenanceMode::Full);
+}
+
+#[tokio::test]
+async fn admin_endpoint_is_disabled_without_a_configured_token() {
+    let m = Maintenance::default();
+    let res = app_with_auth(m.clone(), AdminAuth::disabled())
+        .oneshot(put_mode("full", Some(ADMIN_TOKEN)))
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    assert_eq!(json_body(res).await["error"]["code"], "FORBIDDEN");
+    assert_eq!(m.mode(), MaintenanceMode::Off);
+}
@@ -0,0 +1,118 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use axum::{Extension, Router, http::StatusCode, routing::get};
+use shipyard_web::{Readiness, ServeConfig};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+};
+
+async fn readyz(readiness: Option<Extension<Readiness>>) -> StatusCode {
+    match readiness {
+        Some(Extension(r)) if !r.is_ready() => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    }
+}
+
+fn app() -> Router {
+    Router::new().route("/readyz", get(readyz)).route(
+        "/slow",
+        get(|| async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "done"
+        }),
+    )
+}
+
+/// Minimal HTTP/1.1 GET returning the status code.
+async fn get_status(addr: std::net::SocketAddr, path: &str) -> u16 {
+    let mut stream = TcpStream::connect(addr).await.expect("connect");
+    let req = format!("GET {path} HTTP/1.1\r\nHost: test\r\nConnection: close\r\n\r\n");
+    stream.write_all(req.as_bytes()).await.expect("write");
+
+    let mut buf = Vec::new();
+    stream.read_to_end(&mut buf).await.expect("read");
+    let head = String::from_utf8_lossy(&buf);
+    head.split_whitespace()
+        .nth(1)
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
+#[tokio::test]
+async fn readyz_flips_to_503_during_pre_stop_delay() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+
+    let cfg = ServeConfig {
+        pre_stop_delay: Duration::from_millis(500),
+        drain_timeout: Duration::from_secs(1),
+    };
+    let server = tokio::spawn(shipyard_web::serve_with_shutdown(
+        listener,
+        app(),
+        Readiness::new(),
+        cfg,
+        async move {
+            let _ = stop_rx.await;
+        },
+        || {},
+    ));
+
+    assert_eq!(get_status(addr, "/readyz").await, 200);
+
+    stop_tx.send(()).expect("signal");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    // Still accepting connections, but no longer ready.
+    assert_eq!(get_status(addr, "/readyz").await, 503);
+
+    server.await.expect("join").expect("serve");
+}
+
+#[tokio::test]
+async fn drain_timeout_bounds_shutdown_and_flush_runs() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let addr = listener.local_addr().expect("addr");
+    let (stop_tx, stop_rx) = oneshot::channel::<()>();
+    let flushed = Arc::new(AtomicBool::new(false));
+
+    let cfg = ServeConfig {
+        pre_stop_delay: Duration::ZERO,
+        drain_timeout: Duration::from_millis(200),
+    };
+    let flag = flushed.clone();
+    let server = tokio::spawn(shipyard_web::serve_with_shutdown(
+        listener,
+        app(),
+        Readiness::new(),
+        cfg,
+        async move {
+            let _ = stop_rx.await;
+        },
+        move || flag.store(true, Ordering::SeqCst),
+    ));
+
+    // Start a request that outlives the drain deadline.
+    let slow = tokio::spawn(get_status(addr, "/slow"));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    stop_tx.send(()).expect("signal");
+
+    tokio::time::timeout(Duration::from_secs(2), server)
+        .await
+        .expect("server stops within drain deadline")
+        .expect("join")
+        .expect("serve");
+
+    assert!(flushed.load(Ordering::SeqCst));
+    slow.abort();
+}
@@ -0,0 +1,131 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use shipyard_web::{TenantConfig, TenantId, WebContractConfig};
+use tower::ServiceExt;
+
+fn app(cfg: WebContractConfig) -> Router {
+    shipyard_web::apply_web_contract_with(
+        Router::new().route("/whoami", get(|tenant: TenantId| async move { tenant.0 })),
+        cfg,
+    )
+}
+
+fn req(tenant: Option<&str>) -> Request<Body> {
+    let mut builder = Request::builder().method("GET").uri("/whoami");
+    if let Some(t) = tenant {
+        builder = builder.header("x-tenant-id", t);
+    }
+    builder.body(Body::empty()).expect("build request")
+}
+
+async fn body_bytes(res: axum::response::Response) -> Vec<u8> {
+    res.into_body()
+        .collect()
+        .await
+        .expect("collect body")
+        .to_bytes()
+        .to_vec()
+}
+
+#[tokio::test]
+async fn extractor_reads_tenant_header() {
+    let res = app(WebContractConfig::default())
+        .oneshot(req(Some("acme-eu")))
+        .await
+        .expect("oneshot");
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_bytes(res).await, b"acme-eu");
+}
+
+#[tokio::test]
+async fn missing_tenant_returns_envelope_error() {
+    let res = app(WebContractConfig::default())
+        .oneshot(req(None))
+        .await
+        .expect("oneshot");
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let header_id = res.headers()["x-request-id"].to_str().unwrap().to_string();
+
+    let v: Value = serde_json::from_slice(&body_bytes(res).await).expect("json");
+    assert_eq!(v["error"]["code"], "TENANT_REQUIRED");
+    assert_eq!(v["error"]["request_id"], header_id.as_str());
+}
+
+#[tokio::test]
+async fn malformed_tenant_is_rejected() {
+    let res = app(WebContractConfig::default())
+        .oneshot(req(Some("acme eu/../")))
+        .await
+        .expect("oneshot");
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let v: Value = serde_json::from_slice(&body_bytes(res).await).expect("json");
+    assert_eq!(v["error"]["code"], "INVALID_TENANT");
+}
+
+#[tokio::test]
+async fn allowlist_rejects_unknown_tenant() {
+    let cfg = WebContractConfig {
+        tenant: TenantConfig {
+            allowed: Some(Arc::new(HashSet::from(["acme".to_string()]))),
+            ..TenantConfig::default()
+        },
+        ..WebContractConfig::default()
+    };
+
+    let ok = app(cfg.clone()).oneshot(req(Some("acme"))).await.unwrap();
+    assert_eq!(ok.status(), StatusCode::OK);
+
+    let res = app(cfg).oneshot(req(Some("globex"))).await.unwrap();
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    let v: Value = serde_json::from_slice(&body_bytes(res).await).expect("json");
+    assert_eq!(v["error"]["code"], "INVALID_TENANT");
+}
+
+#[tokio::test]
+async fn principal_tenant_takes_precedence_over_header() {
+    // An auth layer outside the contract inserts the principal's tenant.
+    let app = app(WebContractConfig::default()).layer(axum::middleware::from_fn(
+        |mut req: axum::extract::Request, next: axum::middleware::Next| async move {
+            req.extensions_mut()
+                .insert(TenantId("from-principal".to_string()));
+            next.run(req).await
+        },
+    ));
+
+    let res = app.oneshot(req(Some("from-header"))).await.unwrap();
+
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(body_bytes(res).await, b"from-principal");
+}
+
+#[test]
+fn tenant_id_validation() {
+    assert!(TenantId::parse("acme_01").is_some());
+    assert!(TenantId::parse("").is_none());
+    assert!(TenantId::parse(&"a".repeat(65)).is_none());
+    assert!(TenantId::parse("ümlaut").is_none());
+}
+
+#[tokio::test]
+async fn resolved_tenant_is_copied_to_the_response() {
+    let res = app(WebContractConfig::default())
+        .oneshot(req(Some("acme")))
+        .await
+        .expect("oneshot");
+
+    assert_eq!(
+        res.extensions().get::<TenantId>(),
+        Some(&TenantId("acme".to_string()))
+    );
+}
@@ -0,0 +1,26 @@
+-- Feature flags: boolean or percentage rollout, with per-tenant overrides.
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+  name          TEXT PRIMARY KEY,
+  kind          TEXT NOT NULL CHECK (kind IN ('BOOLEAN', 'PERCENTAGE')),
+
+  -- BOOLEAN: on/off for everyone
+  enabled       BOOLEAN NOT NULL DEFAULT false,
+  -- PERCENTAGE: share of tenants (stable per tenant) that get the flag
+  percentage    SMALLINT NOT NULL DEFAULT 0 CHECK (percentage BETWEEN 0 AND 100),
+
+  description   TEXT,
+
+  created_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+  updated_at    TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+CREATE TABLE IF NOT EXISTS feature_flag_overrides (
+  flag_name     TEXT NOT NULL REFERENCES feature_flags (name) ON DELETE CASCADE,
+  tenant_id     TEXT NOT NULL,
+  enabled       BOOLEAN NOT NULL,
+
+  updated_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+
+  PRIMARY KEY (flag_name, tenant_id)
+);
@@ -5,23 +5,35 @@
 //! - Works in CI and local environments consistently.
 //! - Keeps migrations service-owned and discoverable.
 
-use std::env;
+use fulfilment_api::config::{self, CONFIG_PREFIX, FulfilmentConfig};
 
-use sqlx::postgres::PgPoolOptions;
+const SERVICE_NAME: &str = "fulfilment-migrate";
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let database_url =
-        env::var("DATABASE_URL").expect("DATABASE_URL must be set to run migrations");
+    // Same config as the service, so one config file serves every binary.
+    let config = shipyard_config::cli::load::<FulfilmentConfig>(CONFIG_PREFIX, "migrate")?
+        .into_inner()
+        .app;
 
-    let pool = PgPoolOptions::new()
+    // Held until `main` returns: dropping it flushes pending telemetry.
+    let _telemetry = shipyard_observability::init(config::observability(
+        &config,
+        config.service_name(SERVICE_NAME),
+    ))?;
+
+    // Migrations are sequential; two connections are plenty whatever the service pool size.
+    let pool = config
+        .database
+        .pool_options()
         .max_connections(2)
-        .connect(&database_url)
+        .min_connections(0)
+        .connect_with(config.database.connect_options()?)
         .await?;
 
     // Uses the folder relative to this crate.
     sqlx::migrate!("./migrations").run(&pool).await?;
 
-    println!("migrations applied successfully");
+    tracing::info!("migrations applied successfully");
     Ok(())
 }
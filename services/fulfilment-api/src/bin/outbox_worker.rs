@@ -1,29 +1,44 @@
-use sqlx::postgres::PgPoolOptions;
-use std::time::Duration;
+use fulfilment_api::{
+    config::{self, CONFIG_PREFIX, FulfilmentConfig},
+    outbox::{delivery::LogSink, worker},
+};
+use shipyard_config::ReloadTriggers;
 
-use fulfilment_api::outbox::{delivery::LogSink, worker};
+const SERVICE_NAME: &str = "fulfilment-outbox-worker";
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    let database_url = std::env::var("DATABASE_URL").expect("DATABASE_URL must be set");
+    let loaded = shipyard_config::cli::load::<FulfilmentConfig>(CONFIG_PREFIX, "outbox-worker")?;
+    let config = &loaded.config.app;
 
-    tracing_subscriber::fmt()
-        .json()
-        .with_target(true)
-        .with_current_span(true)
-        .init();
+    // Held until `main` returns: dropping it flushes pending telemetry.
+    let _telemetry = shipyard_observability::init(config::observability(
+        config,
+        config.service_name(SERVICE_NAME),
+    ))?;
 
     tracing::info!(message = "outbox-worker starting");
 
-    let db = PgPoolOptions::new()
-        .max_connections(5)
-        .connect(&database_url)
-        .await?;
+    // Poll interval, batch size and LOG_FILTER follow config reloads (SIGHUP or config file change).
+    let log_filter = config::reloader(&loaded.config, |c| c.app.log_filter.clone());
+    config::follow_log_filter(log_filter.subscribe());
+    log_filter.spawn(ReloadTriggers::from_env());
+
+    let reloader = config::reloader(&loaded.config, |c| c.service.worker());
+    reloader.spawn(ReloadTriggers::from_env());
 
-    let cfg = worker::WorkerConfig {
-        poll_interval: Duration::from_secs(2),
-        batch_size: 50,
-    };
+    let db = config
+        .database
+        .pool_options()
+        .connect_with(config.database.connect_options()?)
+        .await?;
 
-    worker::run(db, LogSink, cfg).await
+    // Return on SIGTERM/SIGINT (instead of being killed) so `_telemetry` gets dropped.
+    tokio::select! {
+        res = worker::run(db, LogSink, reloader.subscribe()) => res,
+        () = shipyard_web::shutdown_signal() => {
+            tracing::info!(message = "outbox-worker stopped");
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,227 @@
+//! fulfilment-api settings on top of the platform `AppConfig`.
+//!
+//! Read from `FULFILMENT_*` env vars (or `fulfilment_*` keys in the config file).
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use shipyard_config::{
+    AppConfig, ConfigLoader, HumanDuration, KeySpec, LogFormat, LogsExporter, MetricsExporter,
+    OtlpCompression, OtlpProtocol, ReloadOutcome, Reloader, ServiceConfig, ShipyardConfig,
+    Validator,
+};
+use shipyard_observability::{
+    DEFAULT_SHUTDOWN_TIMEOUT, ExporterConfig, LogOutput, MetricsConfig, ObservabilityConfig,
+    Sampler, SamplingConfig, exporter,
+};
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::flags::{self, FeatureFlags};
+use crate::metrics::{self, METRICS};
+
+use crate::outbox::worker::WorkerConfig;
+
+/// Env prefix for the service section.
+pub const CONFIG_PREFIX: &str = "FULFILMENT_";
+
+const DEFAULT_OUTBOX_POLL_INTERVAL: HumanDuration = HumanDuration::from_secs(2);
+const MIN_OUTBOX_POLL_INTERVAL: HumanDuration = HumanDuration::from_millis(10);
+const MAX_OUTBOX_POLL_INTERVAL: HumanDuration = HumanDuration::from_secs(300);
+const DEFAULT_OUTBOX_BATCH_SIZE: i64 = 50;
+const DEFAULT_FLAGS_REFRESH_INTERVAL: HumanDuration = HumanDuration::from_secs(30);
+const MIN_FLAGS_REFRESH_INTERVAL: HumanDuration = HumanDuration::from_secs(1);
+const MAX_FLAGS_REFRESH_INTERVAL: HumanDuration = HumanDuration::from_secs(3600);
+
+pub type Config = ShipyardConfig<FulfilmentConfig>;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FulfilmentConfig {
+    /// Delay between outbox polls, e.g. `"250ms"`, `"2s"`
+    #[serde(default = "default_outbox_poll_interval")]
+    pub outbox_poll_interval: HumanDuration,
+
+    /// Max outbox rows claimed per poll
+    #[serde(default = "default_outbox_batch_size")]
+    pub outbox_batch_size: i64,
+
+    /// How often the feature flag cache is reloaded from Postgres
+    #[serde(default = "default_flags_refresh_interval")]
+    pub flags_refresh_interval: HumanDuration,
+
+    /// Forced flag states, e.g. `orders.new_allocation=on,other=off` (tests, local dev)
+    #[serde(default)]
+    pub flag_overrides: Option<String>,
+}
+
+fn default_outbox_poll_interval() -> HumanDuration {
+    DEFAULT_OUTBOX_POLL_INTERVAL
+}
+
+fn default_outbox_batch_size() -> i64 {
+    DEFAULT_OUTBOX_BATCH_SIZE
+}
+
+fn default_flags_refresh_interval() -> HumanDuration {
+    DEFAULT_FLAGS_REFRESH_INTERVAL
+}
+
+const KEYS: &[KeySpec] = &[
+    KeySpec::new(
+        "outbox_poll_interval",
+        "duration between 10ms and 5m",
+        "Delay between outbox polls",
+    ),
+    KeySpec::new(
+        "outbox_batch_size",
+        "integer > 0",
+        "Max outbox rows claimed per poll",
+    ),
+    KeySpec::new(
+        "flags_refresh_interval",
+        "duration between 1s and 1h",
+        "How often feature flags are reloaded from Postgres",
+    ),
+    KeySpec::new(
+        "flag_overrides",
+        "comma-separated name=on|off",
+        "Force flag states over the DB (tests, local dev)",
+    ),
+];
+
+impl ServiceConfig for FulfilmentConfig {
+    fn keys() -> &'static [KeySpec] {
+        KEYS
+    }
+
+    fn validate(&self, v: &mut Validator<'_>) {
+        v.check_range(
+            "outbox_poll_interval",
+            &self.outbox_poll_interval,
+            MIN_OUTBOX_POLL_INTERVAL..=MAX_OUTBOX_POLL_INTERVAL,
+        );
+        v.check(
+            "outbox_batch_size",
+            self.outbox_batch_size > 0,
+            "integer > 0",
+        );
+        v.check_range(
+            "flags_refresh_interval",
+            &self.flags_refresh_interval,
+            MIN_FLAGS_REFRESH_INTERVAL..=MAX_FLAGS_REFRESH_INTERVAL,
+        );
+        v.check(
+            "flag_overrides",
+            self.flag_overrides
+                .as_deref()
+                .is_none_or(|raw| flags::parse_overrides(raw).is_ok()),
+            "comma-separated name=on|off",
+        );
+    }
+}
+
+impl FulfilmentConfig {
+    /// Empty flag cache carrying `flag_overrides` (refresh it with `FeatureFlags::spawn_refresh`).
+    pub fn feature_flags(&self) -> FeatureFlags {
+        let overrides = self
+            .flag_overrides
+            .as_deref()
+            .and_then(|raw| flags::parse_overrides(raw).ok())
+            .unwrap_or_default();
+        FeatureFlags::new(overrides)
+    }
+
+    pub fn worker(&self) -> WorkerConfig {
+        WorkerConfig {
+            poll_interval: self.outbox_poll_interval.into(),
+            batch_size: self.outbox_batch_size,
+        }
+    }
+}
+
+/// Publish the reloadable subset `select` picks from the config.
+///
+/// Each reload re-runs the full loader (file + env + validation + policy); settings
+/// outside the subset still need a restart. Outcomes are logged and counted in
+/// `config_reloads_total` (only pushed over OTLP for binaries without `/metrics`).
+///
+/// ```ignore
+/// let reloader = config::reloader(&cfg, |c| c.service.worker());
+/// reloader.spawn(ReloadTriggers::from_env());
+/// ```
+pub fn reloader<R>(initial: &Config, select: fn(&Config) -> R) -> Reloader<R>
+where
+    R: Clone + PartialEq + fmt::Debug + Send + Sync + 'static,
+{
+    Reloader::new(select(initial), move || {
+        ConfigLoader::from_env()
+            .load_with::<FulfilmentConfig>(CONFIG_PREFIX)
+            .map(|loaded| select(&loaded.config))
+    })
+    .observe(|outcome: ReloadOutcome| METRICS.record_config_reload(outcome))
+}
+
+/// Observability settings for `service_name` from the platform config (`OTEL_*`, `LOG_FILTER`).
+pub fn observability(app: &AppConfig, service_name: &str) -> ObservabilityConfig {
+    ObservabilityConfig {
+        service_name: service_name.to_string(),
+        service_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        deployment_environment: Some(app.env.as_str().to_string()),
+        resource_attributes: app.resource_attributes(),
+        otlp_endpoint: app.otel_exporter_otlp_endpoint.clone(),
+        exporter: ExporterConfig {
+            protocol: match app.otel_exporter_otlp_protocol {
+                OtlpProtocol::Grpc => exporter::Protocol::Grpc,
+                OtlpProtocol::HttpProtobuf => exporter::Protocol::HttpProtobuf,
+            },
+            headers: app.otlp_headers(),
+            compression: (app.otel_exporter_otlp_compression == OtlpCompression::Gzip)
+                .then_some(exporter::Compression::Gzip),
+            timeout: app.otel_exporter_otlp_timeout.into(),
+            ca_file: app.otel_exporter_otlp_certificate.as_ref().map(Into::into),
+        },
+        log_output: LogOutput {
+            format: match app.log_format() {
+                LogFormat::Json => shipyard_observability::LogFormat::Json,
+                LogFormat::Pretty => shipyard_observability::LogFormat::Pretty,
+                LogFormat::Compact => shipyard_observability::LogFormat::Compact,
+                LogFormat::Logfmt => shipyard_observability::LogFormat::Logfmt,
+            },
+            span_list: app.log_span_list,
+            target: app.log_target,
+            file_line: app.log_file_line,
+            flatten_span_fields: app.log_flatten_span_fields,
+        },
+        log_filter: app.log_filter.clone(),
+        metrics: (app.otel_metrics_exporter == MetricsExporter::Otlp).then(|| MetricsConfig {
+            export_interval: app.otel_metric_export_interval.into(),
+            prometheus: Some(metrics::otlp_source()),
+        }),
+        sampling: SamplingConfig {
+            sampler: Sampler::from_otel(
+                app.otel_traces_sampler.as_str(),
+                app.otel_traces_sampler_arg,
+            )
+            .expect("sampler validated at config load"),
+            keep_errors: app.traces_keep_errors,
+            keep_routes: app.traces_keep_routes(),
+        },
+        export_logs: app.otel_logs_exporter == LogsExporter::Otlp,
+        shutdown_timeout: DEFAULT_SHUTDOWN_TIMEOUT,
+    }
+}
+
+/// Apply `LOG_FILTER` changes to the running subscriber (unset falls back to `RUST_LOG`, then `info`).
+pub fn follow_log_filter(mut rx: watch::Receiver<Option<String>>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        while rx.changed().await.is_ok() {
+            let filter = rx
+                .borrow_and_update()
+                .clone()
+                .or_else(|| std::env::var("RUST_LOG").ok())
+                .unwrap_or_else(|| "info".to_string());
+            if let Err(error) = shipyard_observability::set_log_filter(&filter) {
+                tracing::warn!(%error, filter, "log.filter_rejected");
+            }
+        }
+    })
+}
@@ -0,0 +1,98 @@
+use std::{collections::BTreeMap, time::UNIX_EPOCH};
+
+use axum::{Extension, Json, Router, extract::Query, routing::get};
+use serde::{Deserialize, Serialize};
+
+use shipyard_web::{AdminAuth, TenantId};
+
+use super::{FeatureFlags, Flag, Reason};
+
+/// Path of the admin endpoint mounted by `admin_router`.
+pub const FLAGS_ADMIN_PATH: &str = "/admin/flags";
+
+#[derive(Debug, Deserialize)]
+struct FlagsQuery {
+    /// Also evaluate every flag for this tenant
+    tenant: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlagsStatus {
+    /// Unix seconds of the last successful refresh; `None` before the first one
+    pub refreshed_at: Option<u64>,
+    pub env_overrides: BTreeMap<String, bool>,
+    pub flags: Vec<FlagStatus>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FlagStatus {
+    #[serde(flatten)]
+    pub flag: Flag,
+    /// Evaluation for `?tenant=`, if given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evaluation: Option<Evaluation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Evaluation {
+    pub tenant: String,
+    pub enabled: bool,
+    pub reason: Reason,
+}
+
+/// Admin endpoint: `GET /admin/flags[?tenant=acme]`, the cached flag state.
+///
+/// Read-only; guarded by `auth` like every `/admin/*` route.
+pub fn admin_router<S>(auth: AdminAuth) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    shipyard_web::protect_admin(Router::new().route(FLAGS_ADMIN_PATH, get(get_flags)), auth)
+}
+
+async fn get_flags(
+    Extension(flags): Extension<FeatureFlags>,
+    Query(query): Query<FlagsQuery>,
+) -> Json<FlagsStatus> {
+    let snapshot = flags.snapshot();
+    let tenant = query.tenant.as_deref().and_then(TenantId::parse);
+
+    // Env-only flags are listed too, so overrides for flags missing from the DB are visible.
+    let mut names: Vec<&String> = snapshot.flags.keys().collect();
+    names.extend(
+        snapshot
+            .env_overrides
+            .keys()
+            .filter(|n| !snapshot.flags.contains_key(*n)),
+    );
+
+    let flags = names
+        .into_iter()
+        .map(|name| {
+            let flag = snapshot.flags.get(name).cloned().unwrap_or_else(|| Flag {
+                name: name.clone(),
+                rollout: super::Rollout::Boolean(false),
+                description: None,
+                tenants: BTreeMap::new(),
+            });
+            let evaluation = tenant.as_ref().map(|t| {
+                let (enabled, reason) = snapshot.evaluate(name, Some(t));
+                Evaluation {
+                    tenant: t.as_str().to_string(),
+                    enabled,
+                    reason,
+                }
+            });
+            FlagStatus { flag, evaluation }
+        })
+        .collect();
+
+    Json(FlagsStatus {
+        refreshed_at: snapshot
+            .refreshed_at
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs()),
+        env_overrides: snapshot.env_overrides.clone(),
+        flags,
+    })
+}
@@ -0,0 +1,58 @@
+use std::sync::Arc;
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+
+use shipyard_web::{ApiError, RequestId, TenantId};
+
+use super::{FeatureFlags, FlagSnapshot};
+
+/// Extractor: flag state for this request's tenant.
+///
+/// Takes one snapshot per request, so a flag cannot flip halfway through a handler.
+/// Requires the `FeatureFlags` extension (added by `build_app*`).
+///
+/// ```ignore
+/// async fn create(flags: Flags, ...) {
+///     if flags.is_enabled(flags::NEW_ALLOCATION) { ... }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Flags {
+    snapshot: Arc<FlagSnapshot>,
+    tenant: Option<TenantId>,
+}
+
+impl Flags {
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.snapshot.is_enabled(name, self.tenant.as_ref())
+    }
+
+    pub fn tenant(&self) -> Option<&TenantId> {
+        self.tenant.as_ref()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Flags
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(flags) = parts.extensions.get::<FeatureFlags>() else {
+            let req_id = parts
+                .extensions
+                .get::<RequestId>()
+                .cloned()
+                .unwrap_or_default();
+            tracing::error!("flags.extension_missing");
+            return Err(ApiError::internal(&req_id));
+        };
+
+        Ok(Self {
+            snapshot: flags.snapshot(),
+            tenant: parts.extensions.get::<TenantId>().cloned(),
+        })
+    }
+}
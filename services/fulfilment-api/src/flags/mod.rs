@@ -0,0 +1,179 @@
+//! Feature flags for gradual, per-tenant rollouts.
+//!
+//! Why this exists:
+//! - Risky changes (e.g. new allocation logic) ship dark and are enabled per tenant.
+//! - Flags live in Postgres (`feature_flags`, `feature_flag_overrides`) and are cached
+//!   in-process; handlers never hit the DB to read a flag.
+//!
+//! Evaluation order for `(flag, tenant)`:
+//! 1. env override (`FULFILMENT_FLAG_OVERRIDES`, for tests and local dev)
+//! 2. tenant override row
+//! 3. the flag's rollout: boolean, or percentage of tenants (stable hash of flag + tenant)
+//!
+//! Unknown flags are off. Percentage flags are off for requests without a tenant.
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime},
+};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+
+use shipyard_web::TenantId;
+
+pub mod admin;
+pub mod extract;
+pub mod repo;
+
+pub use extract::Flags;
+
+/// Gates the reworked order allocation.
+pub const NEW_ALLOCATION: &str = "orders.new_allocation";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Rollout {
+    Boolean(bool),
+    /// Share of tenants (0..=100) that get the flag
+    Percentage(u8),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Flag {
+    pub name: String,
+    pub rollout: Rollout,
+    pub description: Option<String>,
+    /// Tenant id → forced state
+    pub tenants: BTreeMap<String, bool>,
+}
+
+/// Why a flag evaluated the way it did (shown on the admin endpoint).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reason {
+    EnvOverride,
+    TenantOverride,
+    Boolean,
+    Percentage,
+    NoTenant,
+    Unknown,
+}
+
+/// Flag definitions as of the last refresh; immutable, shared by reference.
+#[derive(Debug, Clone, Default)]
+pub struct FlagSnapshot {
+    pub flags: BTreeMap<String, Flag>,
+    pub env_overrides: BTreeMap<String, bool>,
+    pub refreshed_at: Option<SystemTime>,
+}
+
+impl FlagSnapshot {
+    pub fn is_enabled(&self, name: &str, tenant: Option<&TenantId>) -> bool {
+        self.evaluate(name, tenant).0
+    }
+
+    pub fn evaluate(&self, name: &str, tenant: Option<&TenantId>) -> (bool, Reason) {
+        if let Some(on) = self.env_overrides.get(name) {
+            return (*on, Reason::EnvOverride);
+        }
+        let Some(flag) = self.flags.get(name) else {
+            return (false, Reason::Unknown);
+        };
+        if let Some(on) = tenant.and_then(|t| flag.tenants.get(t.as_str())) {
+            return (*on, Reason::TenantOverride);
+        }
+        match (flag.rollout, tenant) {
+            (Rollout::Boolean(on), _) => (on, Reason::Boolean),
+            (Rollout::Percentage(pct), Some(t)) => (
+                bucket(name, t.as_str()) < u32::from(pct),
+                Reason::Percentage,
+            ),
+            (Rollout::Percentage(_), None) => (false, Reason::NoTenant),
+        }
+    }
+}
+
+/// Stable 0..100 bucket per (flag, tenant), so raising the percentage only adds tenants.
+fn bucket(flag: &str, tenant: &str) -> u32 {
+    let digest = Sha256::digest(format!("{flag}:{tenant}").as_bytes());
+    u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) % 100
+}
+
+/// In-process flag cache; cheap to clone, refreshed from Postgres in the background.
+#[derive(Clone, Debug, Default)]
+pub struct FeatureFlags {
+    current: Arc<RwLock<Arc<FlagSnapshot>>>,
+}
+
+impl FeatureFlags {
+    /// Empty cache with env overrides applied on top of whatever the DB says.
+    pub fn new(env_overrides: BTreeMap<String, bool>) -> Self {
+        let snapshot = FlagSnapshot {
+            env_overrides,
+            ..FlagSnapshot::default()
+        };
+        Self {
+            current: Arc::new(RwLock::new(Arc::new(snapshot))),
+        }
+    }
+
+    pub fn snapshot(&self) -> Arc<FlagSnapshot> {
+        self.current.read().expect("flag cache poisoned").clone()
+    }
+
+    /// Replace the flag definitions (env overrides are kept).
+    pub fn replace(&self, flags: BTreeMap<String, Flag>) {
+        let mut current = self.current.write().expect("flag cache poisoned");
+        *current = Arc::new(FlagSnapshot {
+            flags,
+            env_overrides: current.env_overrides.clone(),
+            refreshed_at: Some(SystemTime::now()),
+        });
+    }
+
+    /// Reload every flag from Postgres; on error the previous snapshot stays.
+    pub async fn refresh(&self, db: &PgPool) -> Result<usize, sqlx::Error> {
+        let flags = repo::load_all(db).await?;
+        let count = flags.len();
+        self.replace(flags);
+        Ok(count)
+    }
+
+    /// Refresh every `interval` (the first refresh runs immediately).
+    pub fn spawn_refresh(&self, db: PgPool, interval: Duration) -> JoinHandle<()> {
+        let this = self.clone();
+        tokio::spawn(async move {
+            let mut tick = tokio::time::interval(interval);
+            tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                tick.tick().await;
+                if let Err(e) = this.refresh(&db).await {
+                    tracing::warn!(error = %e, "flags.refresh_failed");
+                }
+            }
+        })
+    }
+}
+
+/// `name=on,other=off` → overrides. Accepts `on|off`, `true|false`, `1|0`.
+pub fn parse_overrides(raw: &str) -> Result<BTreeMap<String, bool>, String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("`{pair}` is not name=on|off"))?;
+            let on = match value.trim() {
+                "on" | "true" | "1" => true,
+                "off" | "false" | "0" => false,
+                other => return Err(format!("`{other}` is not on|off")),
+            };
+            Ok((name.trim().to_string(), on))
+        })
+        .collect()
+}
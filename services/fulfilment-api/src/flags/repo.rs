@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use sqlx::PgPool;
+
+use super::{Flag, Rollout};
+
+#[derive(sqlx::FromRow)]
+struct FlagRow {
+    name: String,
+    kind: String,
+    enabled: bool,
+    percentage: i16,
+    description: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+struct OverrideRow {
+    flag_name: String,
+    tenant_id: String,
+    enabled: bool,
+}
+
+/// Every flag with its tenant overrides (two queries; the tables are small).
+pub async fn load_all(db: &PgPool) -> Result<BTreeMap<String, Flag>, sqlx::Error> {
+    let rows: Vec<FlagRow> = sqlx::query_as(
+        r#"
+        SELECT name, kind, enabled, percentage, description
+        FROM feature_flags
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let overrides: Vec<OverrideRow> = sqlx::query_as(
+        r#"
+        SELECT flag_name, tenant_id, enabled
+        FROM feature_flag_overrides
+        "#,
+    )
+    .fetch_all(db)
+    .await?;
+
+    let mut flags: BTreeMap<String, Flag> = rows
+        .into_iter()
+        .map(|r| {
+            let rollout = match r.kind.as_str() {
+                "PERCENTAGE" => Rollout::Percentage(r.percentage.clamp(0, 100) as u8),
+                _ => Rollout::Boolean(r.enabled),
+            };
+            let flag = Flag {
+                name: r.name.clone(),
+                rollout,
+                description: r.description,
+                tenants: BTreeMap::new(),
+            };
+            (r.name, flag)
+        })
+        .collect();
+
+    for o in overrides {
+        if let Some(flag) = flags.get_mut(&o.flag_name) {
+            flag.tenants.insert(o.tenant_id, o.enabled);
+        }
+    }
+
+    Ok(flags)
+}
+
+/// Create or replace a flag definition (used by tests and ops scripts).
+pub async fn upsert_flag(db: &PgPool, flag: &Flag) -> Result<(), sqlx::Error> {
+    let (kind, enabled, percentage) = match flag.rollout {
+        Rollout::Boolean(on) => ("BOOLEAN", on, 0i16),
+        Rollout::Percentage(pct) => ("PERCENTAGE", false, i16::from(pct)),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO feature_flags (name, kind, enabled, percentage, description)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (name) DO UPDATE
+        SET kind = EXCLUDED.kind,
+            enabled = EXCLUDED.enabled,
+            percentage = EXCLUDED.percentage,
+            description = EXCLUDED.description,
+            updated_at = now()
+        "#,
+    )
+    .bind(&flag.name)
+    .bind(kind)
+    .bind(enabled)
+    .bind(percentage)
+    .bind(&flag.description)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
+
+pub async fn set_tenant_override(
+    db: &PgPool,
+    flag_name: &str,
+    tenant_id: &str,
+    enabled: bool,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO feature_flag_overrides (flag_name, tenant_id, enabled)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (flag_name, tenant_id) DO UPDATE
+        SET enabled = EXCLUDED.enabled, updated_at = now()
+        "#,
+    )
+    .bind(flag_name)
+    .bind(tenant_id)
+    .bind(enabled)
+    .execute(db)
+    .await?;
+
+    Ok(())
+}
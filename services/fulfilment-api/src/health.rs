@@ -0,0 +1,86 @@
+//! Readiness checks for fulfilment-api.
+//!
+//! Registered with `shipyard_web::HealthRegistry` and served on `/readyz`:
+//! - `db` (critical): Postgres answers `SELECT 1`
+//! - `migrations` (critical): every embedded migration has been applied
+//! - `outbox_backlog` (non-critical): pending outbox rows stay under a threshold
+
+use std::time::Duration;
+
+use sqlx::{PgPool, migrate::Migrator};
+
+use shipyard_web::{HealthCheck, HealthRegistry};
+
+static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+const OUTBOX_BACKLOG_THRESHOLD: i64 = 1_000;
+
+pub fn registry(db: &PgPool) -> HealthRegistry {
+    let (db_check, migrations_check, outbox_check) = (db.clone(), db.clone(), db.clone());
+
+    HealthRegistry::new()
+        .register(HealthCheck::critical("db", CHECK_TIMEOUT, move || {
+            let db = db_check.clone();
+            async move {
+                sqlx::query("SELECT 1")
+                    .execute(&db)
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| e.to_string())
+            }
+        }))
+        .register(HealthCheck::critical(
+            "migrations",
+            CHECK_TIMEOUT,
+            move || {
+                let db = migrations_check.clone();
+                async move { migrations_applied(&db).await }
+            },
+        ))
+        .register(HealthCheck::non_critical(
+            "outbox_backlog",
+            CHECK_TIMEOUT,
+            move || {
+                let db = outbox_check.clone();
+                async move { outbox_backlog(&db).await }
+            },
+        ))
+}
+
+/// Registry for the DB-free app: readiness always fails with a clear reason.
+pub fn registry_without_db() -> HealthRegistry {
+    HealthRegistry::new().register(HealthCheck::critical("db", CHECK_TIMEOUT, || async {
+        Err("database not configured".to_string())
+    }))
+}
+
+async fn migrations_applied(db: &PgPool) -> Result<(), String> {
+    let expected = MIGRATOR.iter().map(|m| m.version).max().unwrap_or(0);
+
+    let (applied,): (Option<i64>,) =
+        sqlx::query_as("SELECT MAX(version) FROM _sqlx_migrations WHERE success")
+            .fetch_one(db)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    match applied {
+        Some(v) if v >= expected => Ok(()),
+        Some(v) => Err(format!("schema at version {v}, expected {expected}")),
+        None => Err("no migrations applied".to_string()),
+    }
+}
+
+async fn outbox_backlog(db: &PgPool) -> Result<(), String> {
+    let (pending,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM outbox WHERE status = 'PENDING'")
+        .fetch_one(db)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if pending > OUTBOX_BACKLOG_THRESHOLD {
+        return Err(format!(
+            "{pending} pending events (threshold {OUTBOX_BACKLOG_THRESHOLD})"
+        ));
+    }
+    Ok(())
+}
@@ -1,16 +1,26 @@
 //! HTTP metrics middleware.
 //!
-//! Records per-request Prometheus metrics using matched route patterns.
+//! Records per-request Prometheus metrics using matched route patterns,
+//! plus usage of routes marked deprecated via `shipyard_web::deprecate`.
+//! The state decides whether tenants get their own label (see `TenantLabels`).
 //! `/metrics` is excluded to avoid scrape noise.
 
 use axum::{
-    extract::{MatchedPath, Request},
+    extract::{MatchedPath, Request, State},
     middleware::Next,
     response::Response,
 };
 use std::time::Instant;
 
-pub async fn middleware(req: Request, next: Next) -> Response {
+use shipyard_web::{DeprecatedCall, TenantId};
+
+use crate::metrics::TenantLabels;
+
+pub async fn middleware(
+    State(tenant_labels): State<TenantLabels>,
+    req: Request,
+    next: Next,
+) -> Response {
     // NOTE: Own the route string so we can move `req` into `next.run(req)`.
     let route: String = req
         .extensions()
@@ -30,8 +40,21 @@ pub async fn middleware(req: Request, next: Next) -> Response {
     let dur = start.elapsed();
 
     let status = res.status().as_u16();
-
-    crate::metrics::METRICS.record_http_request(&method, &route, status, dur);
+    // Resolved inside the web contract, which this layer wraps: only the response has it.
+    let tenant = res.extensions().get::<TenantId>();
+
+    crate::metrics::METRICS.record_http_request(
+        &method,
+        &route,
+        status,
+        tenant,
+        tenant_labels,
+        dur,
+    );
+
+    if let Some(call) = res.extensions().get::<DeprecatedCall>() {
+        crate::metrics::METRICS.record_deprecated_call(&route, &call.client);
+    }
 
     res
 }
@@ -1,55 +1,104 @@
 //! Top-level router wiring (runtime endpoints + versioned API).
 
-use axum::extract::State;
-use axum::http::{StatusCode, header};
+use std::time::Duration;
+
+use axum::http::header;
 use axum::response::IntoResponse;
-use axum::{Router, middleware, routing::get};
+use axum::{Extension, Router, middleware, routing::get};
 
 use shipyard_config::AppConfig;
+use shipyard_web::{AdminAuth, HealthRegistry, Maintenance, WebContractConfig};
 
 use crate::AppState;
+use crate::flags::{self, FeatureFlags};
 use crate::http::{middleware::http_metrics, v1};
+use crate::metrics::TenantLabels;
+
+const MAINTENANCE_FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Web contract settings derived from service config.
+///
+/// NOTE: starts the maintenance file watch when `MAINTENANCE_FILE` is set
+/// (must be called inside a Tokio runtime in that case).
+pub fn web_contract_config(config: &AppConfig) -> WebContractConfig {
+    let maintenance = Maintenance::new(Duration::from_secs(config.maintenance_retry_after_secs));
+
+    if let Some(path) = &config.maintenance_file {
+        maintenance.watch_file(path.into(), MAINTENANCE_FILE_POLL_INTERVAL);
+    }
+
+    WebContractConfig {
+        maintenance,
+        messages: v1::messages(),
+        ..WebContractConfig::default()
+    }
+}
+
+/// Guard for `/admin/*`: `ADMIN_TOKEN` as a bearer token; disabled when unset.
+pub fn admin_auth(config: &AppConfig) -> AdminAuth {
+    config
+        .admin_token
+        .as_ref()
+        .map_or_else(AdminAuth::disabled, |token| {
+            AdminAuth::bearer(token.expose().trim())
+        })
+}
 
-pub fn build_router() -> Router<AppState> {
-    let app = shipyard_web::apply_web_contract(
+pub fn build_router(
+    health: HealthRegistry,
+    web: WebContractConfig,
+    admin: AdminAuth,
+    flags: FeatureFlags,
+) -> Router<AppState> {
+    let tenant_labels = TenantLabels::for_contract(&web);
+    let app = shipyard_web::apply_web_contract_with(
         Router::new()
             .route("/healthz", get(|| async { "ok" }))
-            .route("/readyz", get(readyz))
+            .route("/readyz", get(shipyard_web::health::readyz))
             .route("/metrics", get(metrics))
-            .nest("/api/v1", v1::router()),
+            .merge(shipyard_web::maintenance_admin_router(admin.clone()))
+            .merge(flags::admin::admin_router(admin))
+            .nest("/api/v1", v1::router())
+            .layer(Extension(health))
+            .layer(Extension(flags)),
+        web,
     );
 
     // NOTE: route_layer runs after route matching, so MatchedPath is available.
-    app.route_layer(middleware::from_fn(http_metrics::middleware))
+    app.route_layer(middleware::from_fn_with_state(
+        tenant_labels,
+        http_metrics::middleware,
+    ))
 }
 
 /// A DB-free router used for fast tests.
 /// - /healthz works
-/// - /readyz returns 503 (because DB is not configured)
+/// - /readyz returns 503 (the `db` check reports "database not configured")
 /// - /metrics works (still useful in tests)
-pub fn build_router_no_db() -> Router<AppConfig> {
-    let app = shipyard_web::apply_web_contract(
+/// - /admin/flags works (no DB refresh, so only env overrides show up)
+pub fn build_router_no_db(
+    web: WebContractConfig,
+    admin: AdminAuth,
+    flags: FeatureFlags,
+) -> Router<AppConfig> {
+    let tenant_labels = TenantLabels::for_contract(&web);
+    let app = shipyard_web::apply_web_contract_with(
         Router::new()
             .route("/healthz", get(|| async { "ok" }))
-            .route(
-                "/readyz",
-                get(|| async { (StatusCode::SERVICE_UNAVAILABLE, "not ready") }),
-            )
+            .route("/readyz", get(shipyard_web::health::readyz))
             .route("/metrics", get(metrics))
-            .nest("/api/v1", v1::router_no_db()),
+            .merge(shipyard_web::maintenance_admin_router(admin.clone()))
+            .merge(flags::admin::admin_router(admin))
+            .nest("/api/v1", v1::router_no_db())
+            .layer(Extension(crate::health::registry_without_db()))
+            .layer(Extension(flags)),
+        web,
     );
 
-    app.route_layer(middleware::from_fn(http_metrics::middleware))
-}
-
-async fn readyz(State(state): State<AppState>) -> (StatusCode, &'static str) {
-    let ok = sqlx::query("SELECT 1").execute(&state.db).await.is_ok();
-
-    if ok {
-        (StatusCode::OK, "ready")
-    } else {
-        (StatusCode::SERVICE_UNAVAILABLE, "not ready")
-    }
+    app.route_layer(middleware::from_fn_with_state(
+        tenant_labels,
+        http_metrics::middleware,
+    ))
 }
 
 async fn metrics() -> impl IntoResponse {
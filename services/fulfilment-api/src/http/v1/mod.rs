@@ -1,6 +1,7 @@
 //! API v1 router.
 use axum::Router;
 use shipyard_config::AppConfig;
+use shipyard_web::Catalogue;
 
 use crate::AppState;
 
@@ -10,6 +11,11 @@ pub fn router() -> Router<AppState> {
     Router::new().nest("/orders", orders::router())
 }
 
+/// Error message translations for v1 routes (on top of the shipyard-web built-ins).
+pub fn messages() -> Catalogue {
+    Catalogue::builtin().with_entries(orders::MESSAGES)
+}
+
 /// DB-free v1 router used in fast tests.
 pub fn router_no_db() -> Router<AppConfig> {
     Router::new().nest("/orders", orders::router_no_db())
@@ -20,8 +20,10 @@ pub async fn get_order(
     Extension(req_id): Extension<RequestId>,
     Path(id): Path<String>,
 ) -> Result<Json<GetOrderResponse>, ApiError> {
-    let uuid = sqlx::types::Uuid::parse_str(&id)
-        .map_err(|_| ApiError::validation(&req_id, "invalid id (expected UUID)"))?;
+    let uuid = sqlx::types::Uuid::parse_str(&id).map_err(|_| {
+        ApiError::validation(&req_id, "invalid id (expected UUID)")
+            .with_message_key("orders.invalid_id", [])
+    })?;
 
     let row = repo::get_order_by_id_tx(&state.db, &req_id, &uuid.to_string()).await?;
 
@@ -12,3 +12,4 @@ mod handlers;
 mod repo;
 mod types;
 mod validate;
+pub(crate) use validate::MESSAGES;
@@ -2,6 +2,50 @@ use shipyard_web::{ApiError, RequestId};
 
 use super::types::{CreateOrderRequest, OrderItem, ValidateOrderRequest};
 
+/// Translations for the validation messages below: `(locale, key, template)`.
+///
+/// English lives next to each check; keys must match the `with_message_key` calls.
+pub(crate) const MESSAGES: &[(&str, &str, &str)] = &[
+    (
+        "de",
+        "orders.external_id_empty",
+        "external_id darf nicht leer sein",
+    ),
+    (
+        "pl",
+        "orders.external_id_empty",
+        "external_id nie może być puste",
+    ),
+    ("de", "orders.items_empty", "items darf nicht leer sein"),
+    ("pl", "orders.items_empty", "items nie może być puste"),
+    (
+        "de",
+        "orders.item_sku_empty",
+        "items[{idx}].sku darf nicht leer sein",
+    ),
+    (
+        "pl",
+        "orders.item_sku_empty",
+        "items[{idx}].sku nie może być puste",
+    ),
+    (
+        "de",
+        "orders.item_qty_not_positive",
+        "items[{idx}].qty muss > 0 sein",
+    ),
+    (
+        "pl",
+        "orders.item_qty_not_positive",
+        "items[{idx}].qty musi być > 0",
+    ),
+    ("de", "orders.invalid_id", "ungültige id (UUID erwartet)"),
+    (
+        "pl",
+        "orders.invalid_id",
+        "nieprawidłowe id (oczekiwano UUID)",
+    ),
+];
+
 pub fn validate_order(req: &ValidateOrderRequest, req_id: &RequestId) -> Result<(), ApiError> {
     validate_items(&req.external_id, &req.items, req_id)
 }
@@ -16,13 +60,14 @@ fn validate_items(
     req_id: &RequestId,
 ) -> Result<(), ApiError> {
     if external_id.trim().is_empty() {
-        return Err(ApiError::validation(
-            req_id,
-            "external_id must not be empty",
-        ));
+        return Err(
+            ApiError::validation(req_id, "external_id must not be empty")
+                .with_message_key("orders.external_id_empty", []),
+        );
     }
     if items.is_empty() {
-        return Err(ApiError::validation(req_id, "items must not be empty"));
+        return Err(ApiError::validation(req_id, "items must not be empty")
+            .with_message_key("orders.items_empty", []));
     }
 
     for (idx, item) in items.iter().enumerate() {
@@ -30,13 +75,14 @@ fn validate_items(
             return Err(ApiError::validation(
                 req_id,
                 format!("items[{idx}].sku must not be empty"),
-            ));
+            )
+            .with_message_key("orders.item_sku_empty", [("idx", idx.to_string())]));
         }
         if item.qty <= 0 {
-            return Err(ApiError::validation(
-                req_id,
-                format!("items[{idx}].qty must be > 0"),
-            ));
+            return Err(
+                ApiError::validation(req_id, format!("items[{idx}].qty must be > 0"))
+                    .with_message_key("orders.item_qty_not_positive", [("idx", idx.to_string())]),
+            );
         }
     }
 
@@ -6,6 +6,9 @@
 use axum::Router;
 use shipyard_config::AppConfig;
 
+pub mod config;
+pub mod flags;
+pub mod health;
 pub mod http;
 pub mod idempotency;
 pub mod metrics;
@@ -22,7 +25,19 @@ pub struct AppState {
 /// Runtime contract: DB is required.
 /// - If you need a DB-free app for fast tests, use `build_app_without_db`.
 pub fn build_app(config: AppConfig, db: sqlx::PgPool) -> Router {
-    http::router::build_router().with_state(AppState { config, db })
+    build_app_with_flags(config, db, flags::FeatureFlags::default())
+}
+
+/// `build_app` with a feature flag cache (refreshed by the caller, see `FeatureFlags::spawn_refresh`).
+pub fn build_app_with_flags(
+    config: AppConfig,
+    db: sqlx::PgPool,
+    flags: flags::FeatureFlags,
+) -> Router {
+    let health = health::registry(&db);
+    let web = http::router::web_contract_config(&config);
+    let admin = http::router::admin_auth(&config);
+    http::router::build_router(health, web, admin, flags).with_state(AppState { config, db })
 }
 
 /// Build an app for fast tests that do not touch the DB.
@@ -34,5 +49,7 @@ pub fn build_app(config: AppConfig, db: sqlx::PgPool) -> Router {
 /// NOTE: Any route that requires DB should not be present in this router, and
 /// `/readyz` should return 503 (not ready).
 pub fn build_app_without_db(config: AppConfig) -> Router {
-    http::router::build_router_no_db().with_state(config)
+    let web = http::router::web_contract_config(&config);
+    let admin = http::router::admin_auth(&config);
+    http::router::build_router_no_db(web, admin, flags::FeatureFlags::default()).with_state(config)
 }
@@ -1,43 +1,45 @@
-use sqlx::postgres::PgPoolOptions;
 use std::{net::SocketAddr, time::Duration};
 
-use shipyard_config::AppConfig;
+use fulfilment_api::config::{self, CONFIG_PREFIX, FulfilmentConfig};
+use shipyard_config::ReloadTriggers;
+use shipyard_web::{Readiness, ServeConfig};
 
 const SERVICE_NAME: &str = "fulfilment-api";
-const DB_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
-const DB_MAX_CONNECTIONS: u32 = 5;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Config (fail fast)
-    let config = AppConfig::from_env()?; // fail fast
+    // Config (fail fast): defaults → SHIPYARD_CONFIG_FILE → env; handles --print-config
+    let loaded = shipyard_config::cli::load::<FulfilmentConfig>(CONFIG_PREFIX, SERVICE_NAME)?;
+    let config = loaded.config.app.clone();
 
     // Service identity (allow override, but keep a stable default)
-    let service_name = std::env::var("OTEL_SERVICE_NAME")
-        .ok()
-        .filter(|s| !s.trim().is_empty())
-        .unwrap_or_else(|| SERVICE_NAME.to_string());
-
-    // Observability
-    shipyard_observability::init(shipyard_observability::ObservabilityConfig {
-        service_name: service_name.clone(),
-        otlp_endpoint: config.otel_exporter_otlp_endpoint.clone(),
-        log_filter: None,
-    });
+    let service_name = config.service_name(SERVICE_NAME);
 
-    // Dependencies (DB)
-    let database_url =
-        std::env::var("DATABASE_URL").expect("DATABASE_URL must be set (e.g. via docker-compose)");
+    // Observability: exporters are flushed when `telemetry` is shut down after serving
+    let telemetry = shipyard_observability::init(config::observability(&config, service_name))?;
+
+    tracing::debug!(config = ?loaded, "config.loaded");
 
-    let db = PgPoolOptions::new()
-        .max_connections(DB_MAX_CONNECTIONS)
-        .acquire_timeout(DB_CONNECT_TIMEOUT)
-        .connect(&database_url)
+    // Runtime-reloadable settings (SIGHUP or config file change)
+    let reloader = config::reloader(&loaded.config, |c| c.app.log_filter.clone());
+    config::follow_log_filter(reloader.subscribe());
+    reloader.spawn(ReloadTriggers::from_env());
+
+    // Dependencies (DB)
+    let db = config
+        .database
+        .pool_options()
+        .connect_with(config.database.connect_options()?)
         .await
         .expect("failed to connect to Postgres");
 
+    // Feature flags: cached in-process, refreshed from Postgres
+    let service = &loaded.config.service;
+    let flags = service.feature_flags();
+    flags.spawn_refresh(db.clone(), service.flags_refresh_interval.into());
+
     // App assembly
-    let app = fulfilment_api::build_app(config.clone(), db);
+    let app = fulfilment_api::build_app_with_flags(config.clone(), db, flags);
 
     // Serve
     let addr = SocketAddr::from(([0, 0, 0, 0], config.service_port));
@@ -45,38 +47,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     tracing::info!(addr = %addr, port = config.service_port, "{} listening", service_name);
 
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    let serve_cfg = ServeConfig {
+        pre_stop_delay: Duration::from_secs(config.shutdown_pre_stop_delay_secs),
+        drain_timeout: Duration::from_secs(config.shutdown_drain_timeout_secs),
+    };
+
+    shipyard_web::serve(listener, app, Readiness::new(), serve_cfg, move || {
+        telemetry.shutdown()
+    })
+    .await?;
 
     tracing::info!("{} stopped", service_name);
 
     Ok(())
 }
-
-async fn shutdown_signal() {
-    #[cfg(unix)]
-    {
-        use tokio::signal::unix::{SignalKind, signal};
-
-        let mut term = signal(SignalKind::terminate()).expect("failed to install SIGTERM handler");
-        let mut int = signal(SignalKind::interrupt()).expect("failed to install SIGINT handler");
-
-        tokio::select! {
-            _ = term.recv() => {
-                tracing::info!("shutdown signal received (SIGTERM)");
-            }
-            _ = int.recv() => {
-                tracing::info!("shutdown signal received (SIGINT)");
-            }
-        }
-    }
-
-    #[cfg(not(unix))]
-    {
-        let _ = tokio::signal::ctrl_c().await;
-        tracing::info!("shutdown signal received");
-    }
-
-    shipyard_observability::shutdown();
-}
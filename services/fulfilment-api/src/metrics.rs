@@ -3,12 +3,20 @@
 //! Contract:
 //! - `/metrics` exposes Prometheus text format
 //! - HTTP metrics:
-//!   - http_requests_total{method,route,status}
-//!   - http_request_duration_seconds_bucket{method,route,status,le}
+//!   - http_requests_total{method,route,status,tenant}
+//!   - http_request_duration_seconds_bucket{method,route,status,tenant,le}
+//!   - http_deprecated_requests_total{route,client}
+//! - Config: config_reloads_total{result} (`applied` | `unchanged` | `failed`)
+//! - With `OTEL_METRICS_EXPORTER=otlp` the same registry is also pushed over OTLP
+//!   (see `otlp_source`), so scraped and pushed series match.
 //!
 //! Notes:
 //! - `/metrics` is excluded from HTTP metrics to avoid scrape noise.
 //! - Route label uses Axum matched route (e.g. `/api/v1/orders/validate`) to avoid high cardinality.
+//! - Tenant ids are client-supplied, so they only become labels when the web contract has
+//!   a tenant allowlist (see `TenantLabels`); otherwise every tenant is `other`.
+//! - Tenant and client labels are bounded: the first `MAX_DYNAMIC_LABELS` values get
+//!   their own label, the rest are folded into `other` (`none` when no tenant was resolved).
 
 use once_cell::sync::Lazy;
 use prometheus_client::{
@@ -20,23 +28,93 @@ use prometheus_client::{
     },
     registry::Registry,
 };
-use std::{sync::Mutex, time::Duration};
+use std::{collections::HashSet, sync::Mutex, time::Duration};
+
+use shipyard_config::ReloadOutcome;
+use shipyard_observability::PrometheusSource;
+use shipyard_web::{TenantId, WebContractConfig};
 
 pub const PROM_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
 
 pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
 
+/// `METRICS` as the source for OTLP metric export.
+pub fn otlp_source() -> PrometheusSource {
+    PrometheusSource::new(|| METRICS.encode())
+}
+
+const MAX_DYNAMIC_LABELS: usize = 100;
+
+/// How resolved tenants show up in the `tenant` label of HTTP metrics.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TenantLabels {
+    /// Any tenant id is accepted, so all are folded into `other`.
+    #[default]
+    Folded,
+    /// Only allowlisted tenants resolve, so each gets its own label.
+    PerTenant,
+}
+
+impl TenantLabels {
+    /// `PerTenant` only when `web` restricts tenants to an allowlist.
+    pub fn for_contract(web: &WebContractConfig) -> Self {
+        if web.tenant.allowed.is_some() {
+            Self::PerTenant
+        } else {
+            Self::Folded
+        }
+    }
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 pub struct HttpLabels {
     pub method: String,
     pub route: String,
     pub status: String,
+    pub tenant: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ReloadLabels {
+    pub result: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct DeprecatedLabels {
+    pub route: String,
+    pub client: String,
+}
+
+/// Caps the number of distinct values a label can take.
+struct BoundedLabels {
+    seen: Mutex<HashSet<String>>,
+}
+
+impl BoundedLabels {
+    fn new() -> Self {
+        Self {
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+
+    fn label(&self, value: &str) -> String {
+        let mut seen = self.seen.lock().expect("label set poisoned");
+        if seen.contains(value) || seen.len() < MAX_DYNAMIC_LABELS {
+            seen.insert(value.to_string());
+            return value.to_string();
+        }
+        "other".to_string()
+    }
 }
 
 pub struct Metrics {
     registry: Mutex<Registry>,
     http_requests_total: Family<HttpLabels, Counter<u64>>,
     http_request_duration_seconds: Family<HttpLabels, Histogram>,
+    http_deprecated_requests_total: Family<DeprecatedLabels, Counter<u64>>,
+    config_reloads_total: Family<ReloadLabels, Counter<u64>>,
+    tenant_labels: BoundedLabels,
+    client_labels: BoundedLabels,
 }
 
 impl Metrics {
@@ -49,6 +127,8 @@ impl Metrics {
                 // 5ms start, x2 growth, 12 buckets (~10s upper range)
                 Histogram::new(exponential_buckets(0.005, 2.0, 12))
             });
+        let http_deprecated_requests_total: Family<DeprecatedLabels, Counter<u64>> =
+            Family::default();
 
         registry.register(
             "http_requests",
@@ -60,11 +140,27 @@ impl Metrics {
             "HTTP request duration in seconds (excluding /metrics).",
             http_request_duration_seconds.clone(),
         );
+        registry.register(
+            "http_deprecated_requests",
+            "Calls to deprecated routes, by client.",
+            http_deprecated_requests_total.clone(),
+        );
+
+        let config_reloads_total: Family<ReloadLabels, Counter<u64>> = Family::default();
+        registry.register(
+            "config_reloads",
+            "Config reload attempts, by result.",
+            config_reloads_total.clone(),
+        );
 
         Self {
             registry: Mutex::new(registry),
             http_requests_total,
             http_request_duration_seconds,
+            http_deprecated_requests_total,
+            config_reloads_total,
+            tenant_labels: BoundedLabels::new(),
+            client_labels: BoundedLabels::new(),
         }
     }
 
@@ -75,11 +171,20 @@ impl Metrics {
         out
     }
 
-    pub fn record_http_request(&self, method: &str, route: &str, status: u16, duration: Duration) {
+    pub fn record_http_request(
+        &self,
+        method: &str,
+        route: &str,
+        status: u16,
+        tenant: Option<&TenantId>,
+        tenant_labels: TenantLabels,
+        duration: Duration,
+    ) {
         let labels = HttpLabels {
             method: method.to_string(),
             route: route.to_string(),
             status: status.to_string(),
+            tenant: self.tenant_label(tenant, tenant_labels),
         };
 
         self.http_requests_total.get_or_create(&labels).inc();
@@ -87,4 +192,30 @@ impl Metrics {
             .get_or_create(&labels)
             .observe(duration.as_secs_f64());
     }
+
+    pub fn record_deprecated_call(&self, route: &str, client: &str) {
+        let labels = DeprecatedLabels {
+            route: route.to_string(),
+            client: self.client_labels.label(client),
+        };
+
+        self.http_deprecated_requests_total
+            .get_or_create(&labels)
+            .inc();
+    }
+
+    pub fn record_config_reload(&self, outcome: ReloadOutcome) {
+        let labels = ReloadLabels {
+            result: outcome.as_str().to_string(),
+        };
+        self.config_reloads_total.get_or_create(&labels).inc();
+    }
+
+    fn tenant_label(&self, tenant: Option<&TenantId>, mode: TenantLabels) -> String {
+        match (tenant, mode) {
+            (Some(t), TenantLabels::PerTenant) => self.tenant_labels.label(t.as_str()),
+            (Some(_), TenantLabels::Folded) => "other".to_string(),
+            (None, _) => "none".to_string(),
+        }
+    }
 }
@@ -1,10 +1,11 @@
 use sqlx::PgPool;
 use std::time::Duration;
+use tokio::sync::watch;
 
 use super::delivery::DeliverySink;
 use super::repo::{claim_batch, mark_failed, mark_sent};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct WorkerConfig {
     pub poll_interval: Duration,
     pub batch_size: i64,
@@ -19,9 +20,14 @@ impl Default for WorkerConfig {
     }
 }
 
-/// Run one polling loop
-pub async fn run(db: PgPool, sink: impl DeliverySink, cfg: WorkerConfig) -> anyhow::Result<()> {
+/// Run one polling loop; `settings` is re-read every tick, so reloads apply without a restart.
+pub async fn run(
+    db: PgPool,
+    sink: impl DeliverySink,
+    mut settings: watch::Receiver<WorkerConfig>,
+) -> anyhow::Result<()> {
     loop {
+        let cfg = settings.borrow_and_update().clone();
         match tick(&db, &sink, cfg.batch_size).await {
             Ok(()) => {}
             Err(e) if is_missing_outbox_table(&e) => {
@@ -30,11 +36,20 @@ pub async fn run(db: PgPool, sink: impl DeliverySink, cfg: WorkerConfig) -> anyh
             }
             Err(e) => {
                 tracing::error!(error = %e, "outbox.worker.tick_failed");
-                tokio::time::sleep(cfg.poll_interval).await;
+                wait(cfg.poll_interval, &mut settings).await;
             }
         }
 
-        tokio::time::sleep(cfg.poll_interval).await;
+        wait(cfg.poll_interval, &mut settings).await;
+    }
+}
+
+/// Sleep for `interval`, cut short by a settings reload (e.g. from `5m` down to `1s`).
+async fn wait(interval: Duration, settings: &mut watch::Receiver<WorkerConfig>) {
+    tokio::select! {
+        () = tokio::time::sleep(interval) => {}
+        // Disabled once the reloader is gone: then only the sleep ends the wait.
+        Ok(()) = settings.changed() => {}
     }
 }
 
@@ -0,0 +1,78 @@
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use shipyard_config::AppConfig;
+use tower::ServiceExt;
+
+const ADMIN_TOKEN: &str = "test-admin-token-0123456789";
+
+fn app(admin_token: Option<&str>) -> Router {
+    let config = AppConfig::from_kv(admin_token.map(|token| ("ADMIN_TOKEN", token))).unwrap();
+    fulfilment_api::build_app_without_db(config)
+}
+
+async fn get(app: Router, uri: &str, token: Option<&str>) -> StatusCode {
+    let mut req = Request::builder().uri(uri);
+    if let Some(token) = token {
+        req = req.header("authorization", format!("Bearer {token}"));
+    }
+    app.oneshot(req.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+        .status()
+}
+
+#[tokio::test]
+async fn maintenance_admin_requires_the_admin_token() {
+    let uri = "/admin/maintenance";
+
+    assert_eq!(
+        get(app(Some(ADMIN_TOKEN)), uri, None).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        get(app(Some(ADMIN_TOKEN)), uri, Some("not-the-admin-token")).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        get(app(Some(ADMIN_TOKEN)), uri, Some(ADMIN_TOKEN)).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn flags_admin_requires_the_admin_token() {
+    let uri = "/admin/flags?tenant=acme";
+
+    assert_eq!(
+        get(app(Some(ADMIN_TOKEN)), uri, None).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        get(app(Some(ADMIN_TOKEN)), uri, Some(ADMIN_TOKEN)).await,
+        StatusCode::OK
+    );
+}
+
+#[tokio::test]
+async fn admin_endpoints_are_disabled_without_admin_token() {
+    for uri in ["/admin/maintenance", "/admin/flags"] {
+        assert_eq!(
+            get(app(None), uri, Some(ADMIN_TOKEN)).await,
+            StatusCode::FORBIDDEN,
+            "{uri}"
+        );
+    }
+}
+
+#[test]
+fn short_admin_tokens_are_rejected_at_load() {
+    let err = AppConfig::from_kv([("ADMIN_TOKEN", "tooshort123")]).unwrap_err();
+    assert!(err.to_string().contains("ADMIN_TOKEN"), "{err}");
+    assert!(
+        !err.to_string().contains("tooshort123"),
+        "secret echoed: {err}"
+    );
+}
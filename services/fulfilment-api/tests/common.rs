@@ -17,3 +17,16 @@ pub async fn send(method: &str, uri: &str) -> Response {
         .await
         .unwrap()
 }
+
+#[allow(dead_code)]
+pub async fn send_with_headers(method: &str, uri: &str, headers: &[(&str, &str)]) -> Response {
+    let mut builder = Request::builder().method(method).uri(uri);
+    for (k, v) in headers {
+        builder = builder.header(*k, *v);
+    }
+
+    app()
+        .oneshot(builder.body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
@@ -7,6 +7,7 @@ fn app() -> Router {
     fulfilment_api::build_app_without_db(shipyard_config::AppConfig::dev())
 }
 
+#[allow(dead_code)]
 pub async fn send_json(method: &str, uri: &str, body: &str) -> Response {
     app()
         .oneshot(
@@ -21,6 +22,26 @@ pub async fn send_json(method: &str, uri: &str, body: &str) -> Response {
         .unwrap()
 }
 
+#[allow(dead_code)]
+pub async fn send_json_with_headers(
+    method: &str,
+    uri: &str,
+    body: &str,
+    headers: &[(&str, &str)],
+) -> Response {
+    let mut builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    for (k, v) in headers {
+        builder = builder.header(*k, *v);
+    }
+    app()
+        .oneshot(builder.body(Body::from(body.to_string())).unwrap())
+        .await
+        .unwrap()
+}
+
 pub async fn body_json(res: Response) -> Value {
     let bytes = res.into_body().collect().await.unwrap().to_bytes();
     serde_json::from_slice(&bytes).unwrap()
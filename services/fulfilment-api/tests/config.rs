@@ -0,0 +1,187 @@
+use fulfilment_api::config::{CONFIG_PREFIX, Config};
+use std::time::Duration;
+
+#[test]
+fn service_section_defaults_match_previous_worker_settings() {
+    let cfg = Config::from_kv(CONFIG_PREFIX, std::iter::empty::<(&str, &str)>()).unwrap();
+    let worker = cfg.service.worker();
+
+    assert_eq!(worker.poll_interval, Duration::from_secs(2));
+    assert_eq!(worker.batch_size, 50);
+    assert_eq!(cfg.app.service_port, 8080);
+}
+
+#[test]
+fn service_section_reads_prefixed_env() {
+    let cfg = Config::from_kv(
+        CONFIG_PREFIX,
+        [
+            ("FULFILMENT_OUTBOX_BATCH_SIZE", "200"),
+            ("SERVICE_PORT", "9000"),
+        ],
+    )
+    .unwrap();
+
+    assert_eq!(cfg.service.outbox_batch_size, 200);
+    assert_eq!(cfg.app.service_port, 9000);
+}
+
+#[test]
+fn service_section_is_validated() {
+    let err = Config::from_kv(CONFIG_PREFIX, [("FULFILMENT_OUTBOX_BATCH_SIZE", "0")]).unwrap_err();
+    assert!(err.to_string().contains("FULFILMENT_OUTBOX_BATCH_SIZE"));
+}
+
+#[test]
+fn poll_interval_takes_units_and_is_range_checked() {
+    let cfg = Config::from_kv(
+        CONFIG_PREFIX,
+        [("FULFILMENT_OUTBOX_POLL_INTERVAL", "250ms")],
+    )
+    .unwrap();
+    assert_eq!(
+        cfg.service.worker().poll_interval,
+        Duration::from_millis(250)
+    );
+
+    let err =
+        Config::from_kv(CONFIG_PREFIX, [("FULFILMENT_OUTBOX_POLL_INTERVAL", "1h")]).unwrap_err();
+    assert!(err.to_string().contains("between 10ms and 5m"), "{err}");
+}
+
+/// `docs/runbooks/config-reference.md` is generated; regenerate with
+/// `UPDATE_CONFIG_DOCS=1 cargo test -p fulfilment-api --test config` (or `make config-docs`).
+#[test]
+fn config_reference_doc_is_up_to_date() {
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("../../docs/runbooks/config-reference.md");
+    let generated = shipyard_config::render_markdown(
+        "Config reference: fulfilment-api",
+        &Config::schema(CONFIG_PREFIX),
+    );
+
+    if std::env::var_os("UPDATE_CONFIG_DOCS").is_some() {
+        std::fs::write(&path, &generated).unwrap();
+    }
+    let on_disk = std::fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        on_disk == generated,
+        "{} is stale; run `make config-docs`",
+        path.display()
+    );
+}
+
+#[tokio::test]
+async fn reloader_publishes_selected_subset_and_counts_outcomes() {
+    use fulfilment_api::{config, metrics::METRICS};
+    use shipyard_config::ReloadOutcome;
+
+    let cfg = Config::from_kv(CONFIG_PREFIX, [("FULFILMENT_OUTBOX_BATCH_SIZE", "200")]).unwrap();
+    let reloader = config::reloader(&cfg, |c| c.service.worker());
+    assert_eq!(reloader.current().batch_size, 200);
+
+    // Reloads read the process environment, where the batch size is the default.
+    assert_eq!(reloader.reload(), ReloadOutcome::Applied);
+    assert_eq!(reloader.subscribe().borrow().batch_size, 50);
+    assert_eq!(reloader.reload(), ReloadOutcome::Unchanged);
+
+    let metrics = METRICS.encode();
+    assert!(
+        metrics.contains("config_reloads_total{result=\"applied\"}"),
+        "{metrics}"
+    );
+    assert!(
+        metrics.contains("config_reloads_total{result=\"unchanged\"}"),
+        "{metrics}"
+    );
+}
+
+#[test]
+fn flag_overrides_are_validated_and_seed_the_cache() {
+    let cfg = Config::from_kv(
+        CONFIG_PREFIX,
+        [("FULFILMENT_FLAG_OVERRIDES", "orders.new_allocation=on")],
+    )
+    .unwrap();
+    let snap = cfg.service.feature_flags().snapshot();
+    assert!(snap.is_enabled(fulfilment_api::flags::NEW_ALLOCATION, None));
+
+    let err = Config::from_kv(
+        CONFIG_PREFIX,
+        [("FULFILMENT_FLAG_OVERRIDES", "new_allocation")],
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("FULFILMENT_FLAG_OVERRIDES"),
+        "{err}"
+    );
+}
+
+#[test]
+fn observability_settings_follow_otel_env() {
+    use fulfilment_api::config;
+    use shipyard_observability::{LogFormat, Sampler, exporter::Protocol};
+
+    let cfg = Config::from_kv(
+        CONFIG_PREFIX,
+        [
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://otelcol:4317"),
+            ("OTEL_TRACES_SAMPLER", "parentbased_traceidratio"),
+            ("OTEL_TRACES_SAMPLER_ARG", "0.05"),
+            ("TRACES_KEEP_ERRORS", "true"),
+            ("TRACES_KEEP_ROUTES", "/api/v1/orders, /api/v1/admin,"),
+        ],
+    )
+    .unwrap();
+    let obs = config::observability(&cfg.app, "fulfilment-api");
+
+    assert_eq!(
+        obs.sampling.sampler,
+        Sampler::ParentBased(Box::new(Sampler::TraceIdRatio(0.05)))
+    );
+    assert!(obs.sampling.keep_errors);
+    assert_eq!(
+        obs.sampling.keep_routes,
+        vec!["/api/v1/orders".to_string(), "/api/v1/admin".to_string()]
+    );
+    assert!(obs.metrics.is_none() && !obs.export_logs);
+    assert_eq!(obs.exporter.protocol, Protocol::Grpc);
+    assert_eq!(obs.exporter.timeout, std::time::Duration::from_secs(10));
+
+    let cfg = Config::from_kv(
+        CONFIG_PREFIX,
+        [
+            (
+                "OTEL_EXPORTER_OTLP_ENDPOINT",
+                "https://otel.example.com:4318",
+            ),
+            ("OTEL_EXPORTER_OTLP_PROTOCOL", "http/protobuf"),
+            ("OTEL_EXPORTER_OTLP_HEADERS", "authorization=Bearer%20t0ken"),
+            ("OTEL_EXPORTER_OTLP_TIMEOUT", "2s"),
+            ("OTEL_EXPORTER_OTLP_CERTIFICATE", "/etc/ssl/otel-ca.pem"),
+        ],
+    )
+    .unwrap();
+    let exporter = config::observability(&cfg.app, "fulfilment-api").exporter;
+    assert_eq!(exporter.protocol, Protocol::HttpProtobuf);
+    assert_eq!(
+        exporter.headers,
+        vec![("authorization".to_string(), "Bearer t0ken".to_string())]
+    );
+    assert_eq!(exporter.timeout, std::time::Duration::from_secs(2));
+    assert_eq!(exporter.ca_file, Some("/etc/ssl/otel-ca.pem".into()));
+    assert!(exporter.compression.is_none());
+    assert!(!format!("{exporter:?}").contains("t0ken"));
+
+    let defaults = Config::from_kv(CONFIG_PREFIX, std::iter::empty::<(&str, &str)>()).unwrap();
+    let obs = config::observability(&defaults.app, "fulfilment-api");
+    assert_eq!(obs.sampling.sampler, Sampler::default());
+    assert!(!obs.sampling.keep_errors && obs.sampling.keep_routes.is_empty());
+    assert_eq!(obs.log_output.format, LogFormat::Pretty, "dev default");
+    assert!(obs.log_output.target && !obs.log_output.flatten_span_fields);
+
+    let err = Config::from_kv(CONFIG_PREFIX, [("OTEL_TRACES_SAMPLER_ARG", "2")]).unwrap_err();
+    assert!(err.to_string().contains("OTEL_TRACES_SAMPLER_ARG"), "{err}");
+    let err = Config::from_kv(CONFIG_PREFIX, [("OTEL_TRACES_SAMPLER", "sometimes")]).unwrap_err();
+    assert!(err.to_string().contains("OTEL_TRACES_SAMPLER"), "{err}");
+}
@@ -1,5 +1,7 @@
 mod common_db;
 
+use shipyard_observability::testing;
+
 #[tokio::test]
 #[ignore] // run via: make test-db
 async fn db_readyz_is_200_with_db() {
@@ -31,3 +33,73 @@ async fn db_create_and_get_order_roundtrip() {
     let got = common_db::body_json(res).await;
     assert_eq!(got["id"], id);
 }
+
+#[tokio::test]
+#[ignore] // run via: make test-db
+async fn db_create_order_emits_span_and_access_log() {
+    let app = common_db::app().await;
+    let telemetry = testing::capture();
+
+    let external_id = format!("ord_db_{}", uuid::Uuid::new_v4());
+    let body = format!(
+        r#"{{"external_id":"{}","items":[{{"sku":"ABC","qty":1}}]}}"#,
+        external_id
+    );
+    let res = common_db::send_json_with_headers(
+        app,
+        "POST",
+        "/api/v1/orders",
+        &body,
+        &[("x-request-id", "req-create-span")],
+    )
+    .await;
+    assert_eq!(res.status(), axum::http::StatusCode::CREATED);
+
+    let span = telemetry
+        .spans()
+        .named("orders.create")
+        .with_field("request_id", "req-create-span")
+        .with_field("external_id", &external_id)
+        .child_of("http.request")
+        .one();
+    let log = telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("status", 201)
+        .one();
+    assert_eq!(log.field("request_id"), Some("req-create-span"));
+    assert_eq!(log.field("trace_id"), span.trace_id.as_deref());
+}
+
+#[tokio::test]
+#[ignore] // run via: make test-db
+async fn db_flags_refresh_reads_definitions_and_tenant_overrides() {
+    use fulfilment_api::flags::{FeatureFlags, Flag, Rollout, repo};
+    use shipyard_web::TenantId;
+
+    let db = common_db::db().await;
+    let name = format!("test.flag_{}", uuid::Uuid::new_v4());
+
+    repo::upsert_flag(
+        &db,
+        &Flag {
+            name: name.clone(),
+            rollout: Rollout::Percentage(0),
+            description: Some("db test".to_string()),
+            tenants: Default::default(),
+        },
+    )
+    .await
+    .unwrap();
+    repo::set_tenant_override(&db, &name, "acme", true)
+        .await
+        .unwrap();
+
+    let flags = FeatureFlags::default();
+    flags.refresh(&db).await.unwrap();
+    let snap = flags.snapshot();
+
+    assert!(snap.is_enabled(&name, Some(&TenantId::parse("acme").unwrap())));
+    assert!(!snap.is_enabled(&name, Some(&TenantId::parse("globex").unwrap())));
+    assert_eq!(snap.flags[&name].description.as_deref(), Some("db test"));
+}
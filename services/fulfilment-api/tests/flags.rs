@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use axum::{Extension, Router, body::Body, http::Request, routing::get};
+use http_body_util::BodyExt;
+use serde_json::Value;
+use shipyard_web::{AdminAuth, TenantId, WebContractConfig};
+use tower::ServiceExt;
+
+use fulfilment_api::flags::{
+    self, FeatureFlags, Flag, Flags, Reason, Rollout, admin::admin_router, parse_overrides,
+};
+
+fn flag(name: &str, rollout: Rollout, tenants: &[(&str, bool)]) -> (String, Flag) {
+    let flag = Flag {
+        name: name.to_string(),
+        rollout,
+        description: None,
+        tenants: tenants.iter().map(|(t, on)| (t.to_string(), *on)).collect(),
+    };
+    (name.to_string(), flag)
+}
+
+fn tenant(id: &str) -> TenantId {
+    TenantId::parse(id).unwrap()
+}
+
+fn cache(env: &[(&str, bool)], defs: Vec<(String, Flag)>) -> FeatureFlags {
+    let flags = FeatureFlags::new(env.iter().map(|(n, on)| (n.to_string(), *on)).collect());
+    flags.replace(defs.into_iter().collect());
+    flags
+}
+
+const ADMIN_TOKEN: &str = "test-admin-token-0123456789";
+
+/// Tenant-resolving app exposing `flags.is_enabled(NEW_ALLOCATION)` and the admin endpoint.
+fn app(flags: FeatureFlags) -> Router {
+    let router = Router::new()
+        .route(
+            "/probe",
+            get(|flags: Flags| async move { flags.is_enabled(flags::NEW_ALLOCATION).to_string() }),
+        )
+        .merge(admin_router(AdminAuth::bearer(ADMIN_TOKEN)))
+        .layer(Extension(flags));
+    shipyard_web::apply_web_contract_with(router, WebContractConfig::default())
+}
+
+async fn get_body(app: Router, uri: &str, tenant: Option<&str>) -> String {
+    let mut req = Request::builder()
+        .uri(uri)
+        .header("authorization", format!("Bearer {ADMIN_TOKEN}"));
+    if let Some(t) = tenant {
+        req = req.header("x-tenant-id", t);
+    }
+    let res = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[test]
+fn evaluation_order_is_env_then_tenant_then_rollout() {
+    let flags = cache(
+        &[("forced", false)],
+        vec![
+            flag("forced", Rollout::Boolean(true), &[]),
+            flag("beta", Rollout::Boolean(false), &[("acme", true)]),
+        ],
+    );
+    let snap = flags.snapshot();
+    let acme = tenant("acme");
+
+    assert_eq!(
+        snap.evaluate("forced", Some(&acme)),
+        (false, Reason::EnvOverride)
+    );
+    assert_eq!(
+        snap.evaluate("beta", Some(&acme)),
+        (true, Reason::TenantOverride)
+    );
+    assert_eq!(
+        snap.evaluate("beta", Some(&tenant("globex"))),
+        (false, Reason::Boolean)
+    );
+    assert_eq!(
+        snap.evaluate("missing", Some(&acme)),
+        (false, Reason::Unknown)
+    );
+}
+
+#[test]
+fn percentage_rollout_is_stable_and_only_grows() {
+    let tenants: Vec<TenantId> = (0..200).map(|i| tenant(&format!("t{i}"))).collect();
+    let enabled_at = |pct: u8| -> Vec<bool> {
+        let snap = cache(&[], vec![flag("ramp", Rollout::Percentage(pct), &[])]).snapshot();
+        tenants
+            .iter()
+            .map(|t| snap.is_enabled("ramp", Some(t)))
+            .collect()
+    };
+
+    let (at_0, at_30, at_60, at_100) = (
+        enabled_at(0),
+        enabled_at(30),
+        enabled_at(60),
+        enabled_at(100),
+    );
+    assert!(at_0.iter().all(|on| !on));
+    assert!(at_100.iter().all(|on| *on));
+    assert!(at_30.iter().zip(&at_60).all(|(a, b)| !a || *b), "30% ⊆ 60%");
+
+    let share = at_30.iter().filter(|on| **on).count();
+    assert!(
+        (30..=90).contains(&share),
+        "~30% of 200 tenants, got {share}"
+    );
+    assert_eq!(at_30, enabled_at(30), "buckets are stable");
+
+    let snap = cache(&[], vec![flag("ramp", Rollout::Percentage(100), &[])]).snapshot();
+    assert_eq!(snap.evaluate("ramp", None), (false, Reason::NoTenant));
+}
+
+#[test]
+fn overrides_parse_from_config_string() {
+    let parsed = parse_overrides("orders.new_allocation=on, legacy=off ,x=1").unwrap();
+    assert_eq!(
+        parsed,
+        BTreeMap::from([
+            ("legacy".to_string(), false),
+            ("orders.new_allocation".to_string(), true),
+            ("x".to_string(), true),
+        ])
+    );
+    assert!(parse_overrides("orders.new_allocation").is_err());
+    assert!(parse_overrides("a=maybe").is_err());
+}
+
+#[tokio::test]
+async fn extractor_evaluates_for_request_tenant() {
+    let flags = cache(
+        &[],
+        vec![flag(
+            flags::NEW_ALLOCATION,
+            Rollout::Boolean(false),
+            &[("acme", true)],
+        )],
+    );
+
+    assert_eq!(
+        get_body(app(flags.clone()), "/probe", Some("acme")).await,
+        "true"
+    );
+    assert_eq!(
+        get_body(app(flags.clone()), "/probe", Some("globex")).await,
+        "false"
+    );
+    assert_eq!(get_body(app(flags), "/probe", None).await, "false");
+}
+
+#[tokio::test]
+async fn admin_endpoint_shows_flags_and_tenant_evaluation() {
+    let flags = cache(
+        &[("local.only", true)],
+        vec![flag(
+            flags::NEW_ALLOCATION,
+            Rollout::Percentage(0),
+            &[("acme", true)],
+        )],
+    );
+
+    let body = get_body(app(flags), "/admin/flags?tenant=acme", None).await;
+    let json: Value = serde_json::from_str(&body).unwrap();
+
+    assert!(json["refreshed_at"].is_u64());
+    assert_eq!(json["env_overrides"]["local.only"], true);
+
+    let alloc = &json["flags"][0];
+    assert_eq!(alloc["name"], flags::NEW_ALLOCATION);
+    assert_eq!(alloc["rollout"]["kind"], "percentage");
+    assert_eq!(alloc["tenants"]["acme"], true);
+    assert_eq!(alloc["evaluation"]["enabled"], true);
+    assert_eq!(alloc["evaluation"]["reason"], "tenant_override");
+
+    // Env-only flags are listed after the DB-backed ones.
+    let env_only = &json["flags"][1];
+    assert_eq!(env_only["name"], "local.only");
+    assert_eq!(env_only["evaluation"]["reason"], "env_override");
+}
+
+#[tokio::test]
+async fn admin_endpoint_rejects_requests_without_the_admin_token() {
+    let res = app(cache(&[], vec![]))
+        .oneshot(
+            Request::builder()
+                .uri("/admin/flags")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), axum::http::StatusCode::UNAUTHORIZED);
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    assert!(!String::from_utf8_lossy(&bytes).contains("flags"));
+}
@@ -1,4 +1,5 @@
 mod common;
+mod common_json;
 use axum::http::StatusCode;
 
 #[tokio::test]
@@ -12,3 +13,15 @@ async fn readyz_returns_503_without_db() {
     let res = common::send("GET", "/readyz").await;
     assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
 }
+
+#[tokio::test]
+async fn readyz_json_reports_db_check_without_db() {
+    let res = common::send_with_headers("GET", "/readyz", &[("accept", "application/json")]).await;
+    assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let v = common_json::body_json(res).await;
+    assert_eq!(v["ready"], false);
+    assert_eq!(v["checks"][0]["name"], "db");
+    assert_eq!(v["checks"][0]["status"], "fail");
+    assert_eq!(v["checks"][0]["critical"], true);
+}
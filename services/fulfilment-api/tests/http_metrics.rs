@@ -0,0 +1,75 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+};
+use fulfilment_api::{flags::FeatureFlags, http::router};
+use http_body_util::BodyExt;
+use shipyard_config::AppConfig;
+use shipyard_web::AdminAuth;
+use tower::ServiceExt;
+
+fn app_with_allowed_tenants(tenants: &[&str]) -> Router {
+    let config = AppConfig::dev();
+    let mut web = router::web_contract_config(&config);
+    web.tenant.allowed = Some(Arc::new(tenants.iter().map(|t| t.to_string()).collect()));
+    router::build_router_no_db(web, AdminAuth::disabled(), FeatureFlags::default())
+        .with_state(config)
+}
+
+async fn healthz_as(app: &Router, tenant: &str) {
+    let req = Request::builder()
+        .uri("/healthz")
+        .header("x-tenant-id", tenant)
+        .body(Body::empty())
+        .unwrap();
+    let res = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+async fn healthz_series() -> Vec<String> {
+    let res = common::send("GET", "/metrics").await;
+    let bytes = res.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .lines()
+        .filter(|l| l.starts_with("http_requests_total{") && l.contains(r#"route="/healthz""#))
+        .map(str::to_string)
+        .collect()
+}
+
+#[tokio::test]
+async fn tenants_are_folded_into_other_without_an_allowlist() {
+    let res = common::send_with_headers("GET", "/healthz", &[("x-tenant-id", "acme-zz")]).await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    let series = healthz_series().await;
+    assert!(
+        series.iter().any(|l| l.contains(r#"tenant="other""#)),
+        "{series:#?}"
+    );
+    assert!(
+        !series.iter().any(|l| l.contains(r#"tenant="acme-zz""#)),
+        "{series:#?}"
+    );
+}
+
+#[tokio::test]
+async fn allowlisted_tenants_keep_their_label_after_junk_ids() {
+    let app = app_with_allowed_tenants(&["acme"]);
+    for i in 0..101 {
+        healthz_as(&app, &format!("junk-{i}")).await;
+    }
+    healthz_as(&app, "acme").await;
+
+    let series = healthz_series().await;
+    assert!(
+        series.iter().any(|l| l.contains(r#"tenant="acme""#)),
+        "{series:#?}"
+    );
+    assert!(!series.iter().any(|l| l.contains("junk-")), "{series:#?}");
+}
@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use fulfilment_api::metrics::{METRICS, TenantLabels, otlp_source};
+use opentelemetry_sdk::metrics::{
+    data::{Histogram, Sum},
+    reader::MetricProducer,
+};
+use shipyard_observability::PrometheusBridge;
+
+/// OTLP export reads the scraped registry, so both report the same series.
+#[test]
+fn otlp_export_reports_the_scraped_series() {
+    METRICS.record_http_request(
+        "POST",
+        "/api/v1/orders/validate",
+        200,
+        None,
+        TenantLabels::Folded,
+        Duration::from_millis(12),
+    );
+    let scraped = METRICS.encode();
+    assert!(scraped.contains("http_requests_total{"), "{scraped}");
+
+    let scope = PrometheusBridge::new(otlp_source())
+        .produce()
+        .expect("bridge the registry");
+
+    let requests = scope
+        .metrics
+        .iter()
+        .find(|m| m.name == "http_requests")
+        .expect("http_requests pushed");
+    let sum = requests
+        .data
+        .as_any()
+        .downcast_ref::<Sum<f64>>()
+        .expect("counter pushed as a monotonic sum");
+    assert!(sum.is_monotonic);
+    let point = sum
+        .data_points
+        .iter()
+        .find(|dp| {
+            dp.attributes
+                .iter()
+                .any(|(k, v)| k.as_str() == "route" && v.as_str() == "/api/v1/orders/validate")
+        })
+        .expect("route label kept");
+    assert_eq!(point.value, 1.0);
+    for label in ["method", "status", "tenant"] {
+        assert!(
+            point.attributes.iter().any(|(k, _)| k.as_str() == label),
+            "{label} missing"
+        );
+    }
+
+    let latency = scope
+        .metrics
+        .iter()
+        .find(|m| m.name == "http_request_duration_seconds")
+        .expect("latency pushed");
+    let histogram = latency
+        .data
+        .as_any()
+        .downcast_ref::<Histogram<f64>>()
+        .expect("histogram pushed as a histogram");
+    let point = &histogram.data_points[0];
+    assert_eq!(point.count, 1);
+    assert_eq!(point.bounds.len(), 12, "same buckets as the scrape");
+    assert_eq!(point.bucket_counts.iter().sum::<u64>(), 1);
+}
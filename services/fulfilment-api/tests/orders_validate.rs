@@ -2,6 +2,7 @@ mod common;
 mod common_json;
 
 use axum::http::StatusCode;
+use shipyard_observability::testing;
 
 #[tokio::test]
 async fn validate_order_valid_payload_returns_200() {
@@ -45,3 +46,71 @@ async fn unknown_route_returns_404_with_request_id_and_not_found_code() {
     assert_eq!(v["error"]["code"], "NOT_FOUND");
     assert!(!v["error"]["request_id"].as_str().unwrap().is_empty());
 }
+
+#[tokio::test]
+async fn validation_message_follows_accept_language_and_keeps_code() {
+    let body = r#"{"external_id":"ord_1","items":[{"sku":"ABC","qty":0}]}"#;
+
+    let res = common_json::send_json_with_headers(
+        "POST",
+        "/api/v1/orders/validate",
+        body,
+        &[("accept-language", "de-DE,de;q=0.9,en;q=0.5")],
+    )
+    .await;
+
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(res.headers()["content-language"], "de");
+
+    let v = common_json::body_json(res).await;
+    assert_eq!(v["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(v["error"]["message"], "items[0].qty muss > 0 sein");
+}
+
+#[tokio::test]
+async fn unsupported_language_falls_back_to_english() {
+    let body = r#"{"external_id":"","items":[]}"#;
+
+    let res = common_json::send_json_with_headers(
+        "POST",
+        "/api/v1/orders/validate",
+        body,
+        &[("accept-language", "fr-FR")],
+    )
+    .await;
+
+    assert!(!res.headers().contains_key("content-language"));
+
+    let v = common_json::body_json(res).await;
+    assert_eq!(v["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(v["error"]["message"], "external_id must not be empty");
+}
+
+#[tokio::test]
+async fn validate_order_span_is_a_child_of_the_request_span() {
+    let telemetry = testing::capture();
+    let body = r#"{"external_id":"ord_span","items":[{"sku":"ABC","qty":1}]}"#;
+
+    let res = common_json::send_json_with_headers(
+        "POST",
+        "/api/v1/orders/validate",
+        body,
+        &[("x-request-id", "req-validate-span")],
+    )
+    .await;
+    assert_eq!(res.status(), StatusCode::OK);
+
+    telemetry
+        .spans()
+        .named("orders.validate")
+        .with_field("request_id", "req-validate-span")
+        .with_field("external_id", "ord_span")
+        .child_of("http.request")
+        .one();
+    telemetry
+        .logs()
+        .message("request.completed")
+        .with_field("request_id", "req-validate-span")
+        .with_field("status", 200)
+        .one();
+}
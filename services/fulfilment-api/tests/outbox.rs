@@ -1,7 +1,15 @@
 // tests/outbox.rs
 mod common_db;
 
+use std::time::Duration;
+
+use async_trait::async_trait;
 use axum::http::StatusCode;
+use fulfilment_api::outbox::{
+    delivery::{DeliveryResult, DeliverySink},
+    worker::{self, WorkerConfig},
+};
+use tokio::sync::{mpsc, watch};
 
 const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
 
@@ -120,3 +128,65 @@ async fn db_idempotent_create_does_not_enqueue_outbox_twice() {
         "idempotent replay must not enqueue a second event"
     );
 }
+
+/// Forwards every delivered payload to the test.
+struct ChannelSink(mpsc::UnboundedSender<serde_json::Value>);
+
+#[async_trait]
+impl DeliverySink for ChannelSink {
+    async fn deliver(&self, _event_type: &str, payload: &serde_json::Value) -> DeliveryResult {
+        let _ = self.0.send(payload.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+#[ignore] // run via: make test-db
+async fn db_worker_reload_cuts_the_current_poll_wait_short() {
+    let app = common_db::app().await;
+    let (delivered_tx, mut delivered) = mpsc::unbounded_channel();
+    let (settings, settings_rx) = watch::channel(WorkerConfig {
+        poll_interval: Duration::from_secs(300),
+        batch_size: 50,
+    });
+    let worker = tokio::spawn(worker::run(
+        common_db::db().await,
+        ChannelSink(delivered_tx),
+        settings_rx,
+    ));
+    // Let the first tick drain what is there; the worker is then waiting out the 5m interval.
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let payload = format!(
+        r#"{{"external_id":"ord_outbox_reload_{}","items":[{{"sku":"ABC","qty":1}}]}}"#,
+        uuid::Uuid::new_v4()
+    );
+    let res = common_db::send_json(app, "POST", "/api/v1/orders", &payload).await;
+    assert_eq!(res.status(), StatusCode::CREATED);
+    let body = common_db::body_json(res).await;
+    let order_id = body["id"].as_str().expect("response must include id");
+
+    settings
+        .send(WorkerConfig {
+            poll_interval: Duration::from_millis(10),
+            batch_size: 50,
+        })
+        .unwrap();
+
+    let delivered_order = tokio::time::timeout(Duration::from_secs(10), async {
+        while let Some(payload) = delivered.recv().await {
+            if payload["order_id"] == order_id {
+                return true;
+            }
+        }
+        false
+    })
+    .await;
+    worker.abort();
+
+    assert_eq!(
+        delivered_order,
+        Ok(true),
+        "new poll interval must apply without waiting out the old one"
+    );
+}